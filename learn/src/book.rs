@@ -0,0 +1,407 @@
+//! Turns the lessons into a static, browsable book: one HTML page per lesson, with the prose
+//! of its comment blocks as text and the code between them as highlighted snippets.
+//!
+//! Snippets can't run on their own, since they build on the code before them. Each one that runs
+//! any code instead gets a link running the whole lesson with only that snippet's output shown,
+//! built from the tour's markers, and every page also gets a link running the whole lesson.
+//!
+//! The pages link to each other, follow the course order, and need nothing but a browser. The
+//! same pages are served by `learn serve`, with the lessons and challenges running on the host
+//! instead of in the Rust Playground.
+
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::{Error, Result};
+use crate::highlight::{self, escape};
+use crate::lesson::Lesson;
+use crate::source::{self, Segment};
+use crate::tour;
+
+pub const STYLE: &str = include_str!("../support/book.css");
+
+/// Where the pages send readers to run the code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Runner {
+    /// The online Rust Playground, for the static book.
+    Playground,
+    /// The host `learn serve` runs on, with an editor for every challenge.
+    Local,
+}
+
+/// Where `learn book` writes the site unless told otherwise.
+pub fn default_dir(root: &Path) -> PathBuf {
+    root.join("target").join("book")
+}
+
+/// The page a lesson gets, e.g. `04-control-flow.html`.
+pub fn page_name(lesson: &Lesson) -> String {
+    format!("{}.html", lesson.slug)
+}
+
+/// Writes the whole site to `out` and returns how many pages it has.
+pub fn generate(lessons: &[Lesson], out: &Path) -> Result<usize> {
+    fs::create_dir_all(out).map_err(|e| Error::io(out, e))?;
+    write(&out.join("style.css"), STYLE)?;
+    write(&out.join("index.html"), &index(lessons, Runner::Playground))?;
+
+    for lesson in lessons {
+        let html = page(lessons, lesson, Runner::Playground)?;
+        write(&out.join(page_name(lesson)), &html)?;
+    }
+
+    Ok(lessons.len() + 1)
+}
+
+fn write(path: &Path, contents: &str) -> Result<()> {
+    fs::write(path, contents).map_err(|e| Error::io(path, e))
+}
+
+pub fn index(lessons: &[Lesson], runner: Runner) -> String {
+    let mut body = String::new();
+    body.push_str("<h1>Learning Rust</h1>\n");
+    body.push_str(
+        "<p>Notes on <a href=\"https://doc.rust-lang.org/book\">The Rust Programming Language</a>, \
+         one lesson at a time. Every lesson is a cargo project in <code>lessons/</code>; \
+         run it with <code>cargo learn run</code> and read along here.</p>\n<ol class=\"toc\" start=\"0\">\n",
+    );
+    for lesson in lessons {
+        writeln!(
+            body,
+            "<li><a href=\"{}\">{}</a> <span class=\"chapter\">chapter {}</span></li>",
+            page_name(lesson),
+            escape(&lesson.metadata.title),
+            escape(&lesson.metadata.book_chapter)
+        )
+        .unwrap();
+    }
+    body.push_str("</ol>\n");
+
+    layout("Learning Rust", lessons, None, runner, &body)
+}
+
+/// The lesson's page, with its prose, code and challenges.
+pub fn page(lessons: &[Lesson], lesson: &Lesson, runner: Runner) -> Result<String> {
+    let path = lesson.dir.join("src").join("main.rs");
+    let contents = fs::read_to_string(&path).map_err(|e| Error::io(&path, e))?;
+    let position = lessons.iter().position(|other| other.slug == lesson.slug);
+    let previous = position.and_then(|i| i.checked_sub(1)).map(|i| &lessons[i]);
+    let next = position.and_then(|i| lessons.get(i + 1));
+
+    let playground = playground_url(&contents, &lesson.edition);
+    let mut body = String::new();
+
+    writeln!(
+        body,
+        "<h1><span class=\"number\">{}</span> {}</h1>",
+        lesson.id(),
+        escape(&lesson.metadata.title)
+    )
+    .unwrap();
+    body.push_str(&about(lessons, lesson, runner, &playground));
+
+    let steps = tour::steps(&contents);
+    for segment in source::segments(&contents) {
+        match segment {
+            Segment::Heading(heading) => {
+                writeln!(body, "<h2>{}</h2>", escape(&heading)).unwrap();
+            }
+            Segment::Prose(lines) => body.push_str(&prose(&lines)),
+            Segment::Code { line, lines } => {
+                body.push_str("<figure class=\"snippet\"><pre><code>");
+                for code in &lines {
+                    body.push_str(&highlight::line(code));
+                    body.push('\n');
+                }
+                write!(body, "</code></pre><figcaption>src/main.rs:{}", line).unwrap();
+                let step = steps
+                    .iter()
+                    .position(|step| step.code.as_ref().is_some_and(|(first, _)| *first == line))
+                    .filter(|&step| steps[step].runs_alone());
+                match (step, runner) {
+                    (Some(step), Runner::Playground) => write!(
+                        body,
+                        " <a href=\"{}\">▶ Run</a>",
+                        playground_url(&tour::focused(&contents, &steps, step), &lesson.edition)
+                    )
+                    .unwrap(),
+                    (Some(step), Runner::Local) => write!(
+                        body,
+                        "<div class=\"console\" data-lesson=\"{}\" data-step=\"{}\">\
+                         <button data-action=\"run\">▶ Run</button>\
+                         <pre class=\"output\" hidden></pre></div>",
+                        lesson.id(),
+                        step
+                    )
+                    .unwrap(),
+                    (None, _) => {}
+                }
+                body.push_str("</figcaption></figure>\n");
+            }
+        }
+    }
+
+    match runner {
+        Runner::Playground => writeln!(
+            body,
+            "<p class=\"run\"><a href=\"{}\">▶ Run the whole lesson in the Rust Playground</a></p>",
+            playground
+        )
+        .unwrap(),
+        Runner::Local => body.push_str(&console(lesson, None)),
+    }
+    body.push_str(&challenges(lesson, runner)?);
+    body.push_str("<nav class=\"pager\">");
+    if let Some(previous) = previous {
+        write!(
+            body,
+            "<a class=\"previous\" href=\"{}\">← {}</a>",
+            page_name(previous),
+            escape(&previous.metadata.title)
+        )
+        .unwrap();
+    }
+    if let Some(next) = next {
+        write!(
+            body,
+            "<a class=\"next\" href=\"{}\">{} →</a>",
+            page_name(next),
+            escape(&next.metadata.title)
+        )
+        .unwrap();
+    }
+    body.push_str("</nav>\n");
+
+    let title = format!("{} - Learning Rust", lesson.metadata.title);
+    Ok(layout(&title, lessons, Some(lesson), runner, &body))
+}
+
+/// The box under a lesson's title: its chapter, prerequisites, and how to run it.
+fn about(lessons: &[Lesson], lesson: &Lesson, runner: Runner, playground: &str) -> String {
+    let mut html = String::from("<div class=\"about\">\n");
+    writeln!(
+        html,
+        "<p>Follows chapter {} of The Rust Programming Language.</p>",
+        escape(&lesson.metadata.book_chapter)
+    )
+    .unwrap();
+
+    let prerequisites: Vec<String> = lesson
+        .metadata
+        .prerequisites
+        .iter()
+        .map(
+            |name| match lessons.iter().find(|other| other.name == *name) {
+                Some(other) => format!(
+                    "<a href=\"{}\">{}</a>",
+                    page_name(other),
+                    escape(&other.metadata.title)
+                ),
+                None => escape(name),
+            },
+        )
+        .collect();
+    if !prerequisites.is_empty() {
+        writeln!(html, "<p>Read first: {}.</p>", prerequisites.join(", ")).unwrap();
+    }
+
+    match runner {
+        Runner::Playground => write!(
+            html,
+            "<p>Run it with <code>cargo learn run {}</code>, or <a href=\"{}\">open it in the Rust Playground</a>.",
+            lesson.id(),
+            playground
+        ),
+        Runner::Local => write!(
+            html,
+            "<p>Run it with <code>cargo learn run {}</code>, or with the button at the end of this page.",
+            lesson.id()
+        ),
+    }
+    .unwrap();
+    if !lesson.metadata.compiles {
+        write!(
+            html,
+            " It intentionally doesn't compile, so you can see the compiler's errors; \
+             <code>cargo learn run {} --fixed</code> runs the corrected version.",
+            lesson.id()
+        )
+        .unwrap();
+    }
+    html.push_str("</p>\n</div>\n");
+
+    html
+}
+
+/// Paragraphs, with `- ` lines turned into lists and `backticks` into code.
+fn prose(lines: &[String]) -> String {
+    let mut html = String::new();
+    let mut paragraph: Vec<&str> = Vec::new();
+    let mut list: Vec<&str> = Vec::new();
+
+    let flush = |html: &mut String, paragraph: &mut Vec<&str>, list: &mut Vec<&str>| {
+        if !paragraph.is_empty() {
+            writeln!(html, "<p>{}</p>", inline(&paragraph.join(" "))).unwrap();
+            paragraph.clear();
+        }
+        if !list.is_empty() {
+            html.push_str("<ul>\n");
+            for item in list.iter() {
+                writeln!(html, "<li>{}</li>", inline(item)).unwrap();
+            }
+            html.push_str("</ul>\n");
+            list.clear();
+        }
+    };
+
+    for line in lines {
+        if line.is_empty() {
+            flush(&mut html, &mut paragraph, &mut list);
+        } else if let Some(item) = line.strip_prefix("- ") {
+            if !paragraph.is_empty() {
+                flush(&mut html, &mut paragraph, &mut list);
+            }
+            list.push(item);
+        } else {
+            if !list.is_empty() {
+                flush(&mut html, &mut paragraph, &mut list);
+            }
+            paragraph.push(line);
+        }
+    }
+    flush(&mut html, &mut paragraph, &mut list);
+
+    html
+}
+
+/// Escapes a line of prose, turning `backticked` text into `<code>`.
+fn inline(text: &str) -> String {
+    let mut html = String::new();
+    for (index, part) in text.split('`').enumerate() {
+        if index % 2 == 1 {
+            write!(html, "<code>{}</code>", escape(part)).unwrap();
+        } else {
+            html.push_str(&escape(part));
+        }
+    }
+    html
+}
+
+fn challenges(lesson: &Lesson, runner: Runner) -> Result<String> {
+    if lesson.metadata.challenges.is_empty() {
+        return Ok(String::new());
+    }
+
+    let mut html = String::from("<h2>Challenges</h2>\n<ul class=\"challenges\">\n");
+    for challenge in &lesson.metadata.challenges {
+        writeln!(
+            html,
+            "<li><strong>{}</strong>: {} <br><code>examples/{}.rs</code>, check it with \
+             <code>cargo learn check {} {}</code></li>",
+            escape(&challenge.name),
+            inline(&challenge.description),
+            escape(&challenge.name),
+            lesson.id(),
+            escape(&challenge.name)
+        )
+        .unwrap();
+        if runner == Runner::Local {
+            let path = lesson.challenge_path(&challenge.name);
+            let source = fs::read_to_string(&path).map_err(|e| Error::io(&path, e))?;
+            html.push_str(&console(lesson, Some((&challenge.name, &source))));
+        }
+    }
+    html.push_str("</ul>\n");
+
+    Ok(html)
+}
+
+/// Buttons that run the lesson, or run and check a challenge edited in place, on the host
+/// `learn serve` runs on, with the output streaming in underneath.
+fn console(lesson: &Lesson, challenge: Option<(&str, &str)>) -> String {
+    let mut html = format!("<div class=\"console\" data-lesson=\"{}\"", lesson.id());
+    match challenge {
+        Some((name, source)) => write!(
+            html,
+            " data-challenge=\"{}\">\n<textarea spellcheck=\"false\" rows=\"{}\">{}</textarea>\n\
+             <button data-action=\"run\">▶ Run</button> <button data-action=\"check\">✓ Check</button> \
+             <button data-action=\"reset\">Reset</button>",
+            escape(name),
+            source.lines().count().clamp(8, 30),
+            escape(source)
+        ),
+        None => {
+            html.push_str(">\n<button data-action=\"run\">▶ Run the whole lesson</button>");
+            if lesson.has_fixed_variant {
+                html.push_str(" <button data-action=\"fixed\">▶ Run the fixed version</button>");
+            }
+            Ok(())
+        }
+    }
+    .unwrap();
+    html.push_str("\n<pre class=\"output\" hidden></pre>\n</div>\n");
+
+    html
+}
+
+/// Wraps a page's body with the sidebar every page shares.
+fn layout(
+    title: &str,
+    lessons: &[Lesson],
+    current: Option<&Lesson>,
+    runner: Runner,
+    body: &str,
+) -> String {
+    let mut sidebar = String::from("<ol start=\"0\">\n");
+    for lesson in lessons {
+        let class = if current.is_some_and(|current| current.slug == lesson.slug) {
+            " class=\"current\""
+        } else {
+            ""
+        };
+        writeln!(
+            sidebar,
+            "<li{}><a href=\"{}\">{}</a></li>",
+            class,
+            page_name(lesson),
+            escape(&lesson.metadata.title)
+        )
+        .unwrap();
+    }
+    sidebar.push_str("</ol>\n");
+    let script = match runner {
+        Runner::Playground => "",
+        Runner::Local => "<script src=\"playground.js\"></script>\n",
+    };
+
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+         <title>{}</title>\n<link rel=\"stylesheet\" href=\"style.css\">\n</head>\n<body>\n\
+         <nav class=\"sidebar\">\n<a class=\"home\" href=\"index.html\">Learning Rust</a>\n{}</nav>\n\
+         <main>\n{}</main>\n{}</body>\n</html>\n",
+        escape(title),
+        sidebar,
+        body,
+        script
+    )
+}
+
+/// A link that opens the code in the Rust Playground, ready to run.
+fn playground_url(code: &str, edition: &str) -> String {
+    let mut encoded = String::with_capacity(code.len() * 2);
+    for byte in code.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => write!(encoded, "%{:02X}", byte).unwrap(),
+        }
+    }
+
+    format!(
+        "https://play.rust-lang.org/?version=stable&amp;mode=debug&amp;edition={}&amp;code={}",
+        edition, encoded
+    )
+}
@@ -0,0 +1,416 @@
+//! A terminal UI for browsing the lessons: the lessons and their challenges on the left, the
+//! selected one's explanations and code on the right, and the output of the last run at the
+//! bottom.
+//!
+//! Runs, checks and hints are the same `learn` commands you'd type yourself, started in the
+//! background with the browser's limits, so they print and record progress exactly as usual.
+
+use std::fs;
+use std::io::{self, IsTerminal};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+use std::time::Duration;
+
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+
+use crate::error::{Error, Result};
+use crate::exec::Limits;
+use crate::lesson::Lesson;
+use crate::progress::{self, Progress};
+use crate::source::{self, Segment};
+
+const HELP: &str =
+    "↑↓ select  r run  f run fixed  c check  h hint  tab switch pane  PgUp/PgDn scroll  q quit";
+
+/// A row of the list on the left.
+enum Entry {
+    Lesson(usize),
+    Challenge(usize, String),
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Pane {
+    Source,
+    Output,
+}
+
+struct Browser<'a> {
+    root: &'a Path,
+    lessons: &'a [Lesson],
+    limits: &'a Limits,
+    entries: Vec<Entry>,
+    list: ListState,
+    progress: Progress,
+    source: Vec<Line<'static>>,
+    output: Vec<String>,
+    /// What's running in the background, and what it sends back when it's done.
+    running: Option<(String, Receiver<String>)>,
+    focus: Pane,
+    source_scroll: u16,
+    output_scroll: u16,
+}
+
+pub fn browse(root: &Path, lessons: &[Lesson], limits: &Limits) -> Result<()> {
+    if !io::stdout().is_terminal() {
+        return Err(Error::NotATerminal);
+    }
+
+    let mut entries = Vec::new();
+    for (index, lesson) in lessons.iter().enumerate() {
+        entries.push(Entry::Lesson(index));
+        entries.extend(
+            lesson
+                .challenges
+                .iter()
+                .map(|challenge| Entry::Challenge(index, challenge.clone())),
+        );
+    }
+
+    let mut browser = Browser {
+        root,
+        lessons,
+        limits,
+        entries,
+        list: ListState::default().with_selected(Some(0)),
+        progress: Progress::load(root)?,
+        source: Vec::new(),
+        output: vec!["Press r to run the selected lesson or challenge.".to_string()],
+        running: None,
+        focus: Pane::Source,
+        source_scroll: 0,
+        output_scroll: 0,
+    };
+    browser.load_source();
+
+    let mut terminal = ratatui::init();
+    let result = browser.run(&mut terminal);
+    ratatui::restore();
+
+    result
+}
+
+impl Browser<'_> {
+    fn run(&mut self, terminal: &mut DefaultTerminal) -> Result<()> {
+        loop {
+            terminal
+                .draw(|frame| self.draw(frame))
+                .map_err(Error::Terminal)?;
+
+            if let Some((_, done)) = &self.running {
+                if let Ok(output) = done.try_recv() {
+                    self.output = output.lines().map(str::to_string).collect();
+                    self.output_scroll = 0;
+                    self.running = None;
+                    self.progress = Progress::load(self.root)?;
+                }
+            }
+
+            if !event::poll(Duration::from_millis(100)).map_err(Error::Terminal)? {
+                continue;
+            }
+            let Event::Key(key) = event::read().map_err(Error::Terminal)? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                KeyCode::Up | KeyCode::Char('k') => self.select(-1),
+                KeyCode::Down | KeyCode::Char('j') => self.select(1),
+                KeyCode::Tab => {
+                    self.focus = match self.focus {
+                        Pane::Source => Pane::Output,
+                        Pane::Output => Pane::Source,
+                    }
+                }
+                KeyCode::PageUp => self.scroll(-10),
+                KeyCode::PageDown => self.scroll(10),
+                KeyCode::Home => self.scroll(i32::MIN / 2),
+                KeyCode::Char('r') => self.launch(Action::Run),
+                KeyCode::Char('f') => self.launch(Action::RunFixed),
+                KeyCode::Char('c') => self.launch(Action::Check),
+                KeyCode::Char('h') => self.launch(Action::Hint),
+                _ => {}
+            }
+        }
+    }
+
+    fn selected(&self) -> &Entry {
+        &self.entries[self.list.selected().unwrap_or(0)]
+    }
+
+    fn select(&mut self, by: isize) {
+        let current = self.list.selected().unwrap_or(0);
+        let next = current
+            .saturating_add_signed(by)
+            .min(self.entries.len() - 1);
+        if next != current {
+            self.list.select(Some(next));
+            self.load_source();
+        }
+    }
+
+    fn scroll(&mut self, by: i32) {
+        let scroll = match self.focus {
+            Pane::Source => &mut self.source_scroll,
+            Pane::Output => &mut self.output_scroll,
+        };
+        *scroll = (i32::from(*scroll) + by).clamp(0, i32::from(u16::MAX)) as u16;
+    }
+
+    /// Renders the selected lesson's explanations and code, or the selected challenge.
+    fn load_source(&mut self) {
+        self.source_scroll = 0;
+        self.source = match self.selected() {
+            Entry::Lesson(index) => {
+                let lesson = &self.lessons[*index];
+                let path = lesson.dir.join("src").join("main.rs");
+                match fs::read_to_string(&path) {
+                    Ok(contents) => lesson_lines(lesson, &contents),
+                    Err(e) => vec![Line::raw(Error::io(path, e).to_string())],
+                }
+            }
+            Entry::Challenge(index, challenge) => {
+                let lesson = &self.lessons[*index];
+                let path = lesson.challenge_path(challenge);
+                match fs::read_to_string(&path) {
+                    Ok(contents) => challenge_lines(lesson, challenge, &contents),
+                    Err(e) => vec![Line::raw(Error::io(path, e).to_string())],
+                }
+            }
+        };
+    }
+
+    /// Starts the `learn` command for the action in the background.
+    fn launch(&mut self, action: Action) {
+        if self.running.is_some() {
+            return;
+        }
+        let (lesson, challenge) = match self.selected() {
+            Entry::Lesson(index) => (&self.lessons[*index], None),
+            Entry::Challenge(index, challenge) => (&self.lessons[*index], Some(challenge.clone())),
+        };
+
+        let mut args = self.limits.args();
+        match (action, &challenge) {
+            (Action::Run, None) => args.extend(["run".to_string(), lesson.id()]),
+            (Action::Run, Some(challenge)) => args.extend([
+                "run".to_string(),
+                lesson.id(),
+                "--example".to_string(),
+                challenge.clone(),
+            ]),
+            (Action::RunFixed, None) => {
+                args.extend(["run".to_string(), lesson.id(), "--fixed".to_string()])
+            }
+            (Action::Check, _) => {
+                args.extend(["check".to_string(), lesson.id()]);
+                args.extend(challenge.clone());
+            }
+            (Action::Hint, Some(challenge)) => {
+                args.extend(["hint".to_string(), lesson.id(), challenge.clone()])
+            }
+            (Action::RunFixed, Some(_)) | (Action::Hint, None) => {
+                self.output = vec![match action {
+                    Action::Hint => "Select a challenge to get a hint for it.".to_string(),
+                    _ => "Only lessons have a fixed version.".to_string(),
+                }];
+                return;
+            }
+        }
+
+        let label = format!("learn {}", args[3..].join(" "));
+        let program = std::env::current_exe().unwrap_or_else(|_| PathBuf::from("learn"));
+        let root = self.root.to_path_buf();
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            let output = Command::new(program).args(&args).current_dir(root).output();
+            let text = match output {
+                Ok(output) => {
+                    let mut text = String::from_utf8_lossy(&output.stdout).into_owned();
+                    text.push_str(&String::from_utf8_lossy(&output.stderr));
+                    if text.trim().is_empty() {
+                        text = "(nothing printed)".to_string();
+                    }
+                    text
+                }
+                Err(e) => format!("error: couldn't start learn: {}", e),
+            };
+            let _ = sender.send(text);
+        });
+
+        self.output = vec![format!("Running {} ...", label)];
+        self.output_scroll = 0;
+        self.running = Some((label, receiver));
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [main, output, help] = Layout::vertical([
+            Constraint::Min(8),
+            Constraint::Percentage(35),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+        let [list, source] =
+            Layout::horizontal([Constraint::Length(34), Constraint::Min(20)]).areas(main);
+
+        self.draw_list(frame, list);
+
+        let title = match self.selected() {
+            Entry::Lesson(index) => {
+                let lesson = &self.lessons[*index];
+                format!(" {} {} ", lesson.id(), lesson.metadata.title)
+            }
+            Entry::Challenge(index, challenge) => {
+                format!(" {} {} ", self.lessons[*index].id(), challenge)
+            }
+        };
+        frame.render_widget(
+            Paragraph::new(self.source.clone())
+                .block(pane(title, self.focus == Pane::Source))
+                .wrap(Wrap { trim: false })
+                .scroll((self.source_scroll, 0)),
+            source,
+        );
+
+        let title = match &self.running {
+            Some((label, _)) => format!(" Output: running {} ", label),
+            None => " Output ".to_string(),
+        };
+        frame.render_widget(
+            Paragraph::new(
+                self.output
+                    .iter()
+                    .map(|line| Line::raw(line.clone()))
+                    .collect::<Vec<_>>(),
+            )
+            .block(pane(title, self.focus == Pane::Output))
+            .wrap(Wrap { trim: false })
+            .scroll((self.output_scroll, 0)),
+            output,
+        );
+
+        frame.render_widget(
+            Paragraph::new(HELP).style(Style::default().fg(Color::DarkGray)),
+            help,
+        );
+    }
+
+    fn draw_list(&mut self, frame: &mut Frame, area: Rect) {
+        let items: Vec<ListItem> = self
+            .entries
+            .iter()
+            .map(|entry| match entry {
+                Entry::Lesson(index) => {
+                    let lesson = &self.lessons[*index];
+                    let mark = progress::lesson_mark(lesson, &self.progress);
+                    ListItem::new(format!(
+                        "[{}] {} {}",
+                        mark,
+                        lesson.id(),
+                        lesson.metadata.title
+                    ))
+                }
+                Entry::Challenge(index, challenge) => {
+                    let lesson = &self.lessons[*index];
+                    let mark = progress::challenge_mark(lesson, challenge, &self.progress);
+                    ListItem::new(format!("    [{}] {}", mark, challenge))
+                        .style(Style::default().fg(Color::Gray))
+                }
+            })
+            .collect();
+
+        let list = List::new(items)
+            .block(Block::bordered().title(" Lessons "))
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(list, area, &mut self.list);
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Action {
+    Run,
+    RunFixed,
+    Check,
+    Hint,
+}
+
+fn pane(title: String, focused: bool) -> Block<'static> {
+    let style = if focused {
+        Style::default().fg(Color::Cyan)
+    } else {
+        Style::default()
+    };
+    Block::bordered().title(title).border_style(style)
+}
+
+/// The lesson's headings, explanations and code, each styled differently.
+fn lesson_lines(lesson: &Lesson, contents: &str) -> Vec<Line<'static>> {
+    let mut lines = vec![Line::styled(
+        format!(
+            "Chapter {} of the Rust Book{}",
+            lesson.metadata.book_chapter,
+            if lesson.metadata.compiles {
+                ""
+            } else {
+                "; doesn't compile on purpose, press f to run the fixed version"
+            }
+        ),
+        Style::default().fg(Color::DarkGray),
+    )];
+
+    for segment in source::segments(contents) {
+        lines.push(Line::default());
+        match segment {
+            Segment::Heading(heading) => lines.push(Line::styled(
+                heading,
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            )),
+            Segment::Prose(prose) => lines.extend(prose.into_iter().map(Line::raw)),
+            Segment::Code { line, lines: code } => lines.extend(code_lines(line, code)),
+        }
+    }
+
+    lines
+}
+
+fn challenge_lines(lesson: &Lesson, challenge: &str, contents: &str) -> Vec<Line<'static>> {
+    let mut lines = vec![
+        Line::raw(lesson.description(challenge).to_string()),
+        Line::styled(
+            "r runs it, c checks it, h reveals a hint",
+            Style::default().fg(Color::DarkGray),
+        ),
+        Line::default(),
+    ];
+    lines.extend(code_lines(
+        1,
+        contents.lines().map(str::to_string).collect(),
+    ));
+
+    lines
+}
+
+fn code_lines(first: usize, code: Vec<String>) -> impl Iterator<Item = Line<'static>> {
+    let width = (first + code.len()).to_string().len();
+    code.into_iter().enumerate().map(move |(offset, code)| {
+        Line::from(vec![
+            Span::styled(
+                format!("{:>width$} ", first + offset),
+                Style::default().fg(Color::DarkGray),
+            ),
+            Span::styled(code, Style::default().fg(Color::Cyan)),
+        ])
+    })
+}
@@ -0,0 +1,15 @@
+use std::process::Command;
+
+use crate::lesson::Lesson;
+
+/// A `cargo <subcommand>` invocation against a single lesson's manifest.
+pub fn command(subcommand: &str, lesson: &Lesson) -> Command {
+    // `cargo learn` sets $CARGO, so nested builds use the same toolchain as the runner.
+    let cargo = std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    let mut command = Command::new(cargo);
+    command
+        .arg(subcommand)
+        .arg("--manifest-path")
+        .arg(lesson.manifest_path());
+    command
+}
@@ -0,0 +1,387 @@
+//! Checks that a challenge asking for one implementation per loop construct really has them, using
+//! `checks/<challenge>/loops.toml`:
+//!
+//! ```toml
+//! function = "calculate_factorial"
+//! constructs = ["loop", "while", "for"]
+//! inputs = [0, 1, 5, 12]
+//! ```
+//!
+//! The learner's file is parsed, and every function whose name starts with `function` counts as an
+//! implementation of the construct it uses. Once every construct has its own implementation, they
+//! are called with every input and have to agree.
+
+use std::fmt::Write as _;
+use std::fs;
+
+use serde::Deserialize;
+use syn::visit::{self, Visit};
+
+use super::Outcome;
+use crate::error::{Error, Result};
+use crate::exec::Limits;
+use crate::harness::{self, Module};
+use crate::lesson::Lesson;
+
+pub const LOOPS: &str = "loops.toml";
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Spec {
+    /// The name every implementation starts with, e.g. `calculate_factorial_while`.
+    function: String,
+    constructs: Vec<Construct>,
+    /// What every implementation is called with.
+    inputs: Vec<i64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Construct {
+    Loop,
+    While,
+    For,
+}
+
+impl Construct {
+    fn keyword(self) -> &'static str {
+        match self {
+            Construct::Loop => "loop",
+            Construct::While => "while",
+            Construct::For => "for",
+        }
+    }
+}
+
+/// A function of the learner's and the loop constructs in its body.
+struct Implementation {
+    name: String,
+    uses: Vec<Construct>,
+}
+
+/// Returns `None` when the challenge doesn't ask for particular loops.
+pub fn check(lesson: &Lesson, challenge: &str, limits: &Limits) -> Result<Option<Outcome>> {
+    let path = lesson.checks_dir(challenge).join(LOOPS);
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let contents = fs::read_to_string(&path).map_err(|e| Error::io(&path, e))?;
+    let spec: Spec = toml::from_str(&contents).map_err(|source| Error::CheckSpec {
+        path: path.clone(),
+        source,
+    })?;
+
+    let source = lesson.challenge_path(challenge);
+    let contents = fs::read_to_string(&source).map_err(|e| Error::io(&source, e))?;
+    // A file that doesn't parse doesn't compile either, which the other stages report in full.
+    let Ok(file) = syn::parse_file(&contents) else {
+        return Ok(Some(Outcome::Incomplete(vec![
+            "the challenge doesn't compile, so its loops can't be checked".to_string(),
+        ])));
+    };
+
+    let implementations = implementations(&file, &spec.function);
+    let chosen = match choose(&implementations, &spec.constructs) {
+        Ok(chosen) => chosen,
+        Err(missing) => {
+            let problems = missing
+                .iter()
+                .map(|construct| {
+                    format!(
+                        "no `{}` function of its own uses `{}` yet",
+                        spec.function,
+                        construct.keyword()
+                    )
+                })
+                .collect();
+            return Ok(Some(Outcome::Incomplete(problems)));
+        }
+    };
+
+    let tests = lesson.scratch_dir(challenge).join("loops.rs");
+    fs::create_dir_all(lesson.scratch_dir(challenge)).map_err(|e| Error::io(&tests, e))?;
+    fs::write(&tests, agreement_test(&chosen, &spec.inputs)).map_err(|e| Error::io(&tests, e))?;
+
+    let modules = [Module {
+        name: "loops",
+        path: tests,
+    }];
+    let run = harness::run(lesson, challenge, &modules, limits)?;
+    Ok(Some(Outcome::of_tests(run)))
+}
+
+/// Every function named `<prefix>...`, with the loops it uses, in the order they're written.
+fn implementations(file: &syn::File, prefix: &str) -> Vec<Implementation> {
+    #[derive(Default)]
+    struct Loops(Vec<Construct>);
+
+    impl<'ast> Visit<'ast> for Loops {
+        fn visit_expr_loop(&mut self, node: &'ast syn::ExprLoop) {
+            self.0.push(Construct::Loop);
+            visit::visit_expr_loop(self, node);
+        }
+
+        fn visit_expr_while(&mut self, node: &'ast syn::ExprWhile) {
+            self.0.push(Construct::While);
+            visit::visit_expr_while(self, node);
+        }
+
+        fn visit_expr_for_loop(&mut self, node: &'ast syn::ExprForLoop) {
+            self.0.push(Construct::For);
+            visit::visit_expr_for_loop(self, node);
+        }
+    }
+
+    file.items
+        .iter()
+        .filter_map(|item| match item {
+            syn::Item::Fn(function) if function.sig.ident.to_string().starts_with(prefix) => {
+                let mut loops = Loops::default();
+                loops.visit_block(&function.block);
+                Some(Implementation {
+                    name: function.sig.ident.to_string(),
+                    uses: loops.0,
+                })
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Picks a different implementation for every construct, or returns the constructs that are left
+/// without one. A function named after its construct, like `calculate_factorial_while`, is
+/// preferred, then one that uses nothing else.
+fn choose<'a>(
+    implementations: &'a [Implementation],
+    constructs: &[Construct],
+) -> std::result::Result<Vec<(Construct, &'a str)>, Vec<Construct>> {
+    let candidates: Vec<Vec<&str>> = constructs
+        .iter()
+        .map(|&construct| {
+            let mut candidates: Vec<&Implementation> = implementations
+                .iter()
+                .filter(|implementation| implementation.uses.contains(&construct))
+                .collect();
+            candidates.sort_by_key(|implementation| {
+                (
+                    !implementation.name.ends_with(construct.keyword()),
+                    implementation.uses.iter().any(|used| *used != construct),
+                )
+            });
+            candidates
+                .into_iter()
+                .map(|implementation| implementation.name.as_str())
+                .collect()
+        })
+        .collect();
+
+    let mut chosen = Vec::new();
+    if assign(&candidates, &mut chosen) {
+        return Ok(constructs.iter().copied().zip(chosen).collect());
+    }
+
+    // Either nothing uses a construct at all, or the same function is the only one for several.
+    let unused: Vec<Construct> = constructs
+        .iter()
+        .zip(&candidates)
+        .filter(|(_, candidates)| candidates.is_empty())
+        .map(|(construct, _)| *construct)
+        .collect();
+    Err(if unused.is_empty() {
+        constructs.to_vec()
+    } else {
+        unused
+    })
+}
+
+/// Tries the candidates of every construct in turn, backing up when a later construct is left
+/// with only functions that were already picked.
+fn assign<'a>(candidates: &[Vec<&'a str>], chosen: &mut Vec<&'a str>) -> bool {
+    let Some(options) = candidates.get(chosen.len()) else {
+        return true;
+    };
+    for &name in options {
+        if chosen.contains(&name) {
+            continue;
+        }
+        chosen.push(name);
+        if assign(candidates, chosen) {
+            return true;
+        }
+        chosen.pop();
+    }
+    false
+}
+
+/// A test calling every chosen implementation with every input, comparing what they return.
+fn agreement_test(chosen: &[(Construct, &str)], inputs: &[i64]) -> String {
+    let mut source = String::new();
+    writeln!(source, "#[test]\nfn every_loop_gives_the_same_results() {{").unwrap();
+    writeln!(source, "    let inputs: &[i64] = &{:?};", inputs).unwrap();
+    writeln!(source, "    for &input in inputs {{").unwrap();
+    writeln!(source, "        let results = [").unwrap();
+    for (construct, name) in chosen {
+        writeln!(
+            source,
+            "            ({:?}, {:?}, {}(input.try_into().unwrap()).to_string()),",
+            construct.keyword(),
+            name,
+            name
+        )
+        .unwrap();
+    }
+    writeln!(source, "        ];").unwrap();
+    writeln!(
+        source,
+        "        for (construct, name, result) in &results[1..] {{
+            assert_eq!(
+                result, &results[0].2,
+                \"{{}}({{}}), using `{{}}`, disagrees with {{}}({{}}), using `{{}}`\",
+                name, input, construct, results[0].1, input, results[0].0
+            );
+        }}
+    }}
+}}"
+    )
+    .unwrap();
+    source
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chosen(
+        source: &str,
+        constructs: &[Construct],
+    ) -> std::result::Result<Vec<(Construct, String)>, Vec<Construct>> {
+        let file = syn::parse_file(source).unwrap();
+        let implementations = implementations(&file, "calculate_factorial");
+        choose(&implementations, constructs).map(|chosen| {
+            chosen
+                .into_iter()
+                .map(|(construct, name)| (construct, name.to_string()))
+                .collect()
+        })
+    }
+
+    const ALL: &[Construct] = &[Construct::Loop, Construct::While, Construct::For];
+
+    #[test]
+    fn one_function_per_construct() {
+        let source = "
+            fn calculate_factorial_a(n: u64) -> u64 { let mut f = 1; for i in 1..=n { f *= i; } f }
+            fn calculate_factorial_b(n: u64) -> u64 { let mut f = 1; let mut i = 1; while i <= n { f *= i; i += 1; } f }
+            fn calculate_factorial_c(n: u64) -> u64 { let mut f = 1; let mut i = 1; loop { if i > n { break f; } f *= i; i += 1; } }
+            fn main() { for _ in 0..1 {} }
+        ";
+        assert_eq!(
+            chosen(source, ALL),
+            Ok(vec![
+                (Construct::Loop, "calculate_factorial_c".to_string()),
+                (Construct::While, "calculate_factorial_b".to_string()),
+                (Construct::For, "calculate_factorial_a".to_string()),
+            ])
+        );
+    }
+
+    #[test]
+    fn a_missing_construct_is_reported() {
+        let source = "
+            fn calculate_factorial(n: u64) -> u64 { let mut f = 1; for i in 1..=n { f *= i; } f }
+            fn calculate_factorial_while(n: u64) -> u64 { let mut f = 1; let mut i = 1; while i <= n { f *= i; i += 1; } f }
+            fn helper() { loop { break; } }
+        ";
+        assert_eq!(chosen(source, ALL), Err(vec![Construct::Loop]));
+        assert_eq!(chosen("fn main() {}", ALL), Err(ALL.to_vec()));
+    }
+
+    #[test]
+    fn one_function_cant_stand_for_several_constructs() {
+        let source = "
+            fn calculate_factorial(n: u64) -> u64 {
+                let mut f = 1;
+                for i in 1..=n { f *= i; }
+                let mut i = 0;
+                while i < 1 { i += 1; }
+                loop { break f; }
+            }
+        ";
+        assert_eq!(chosen(source, ALL), Err(ALL.to_vec()));
+    }
+
+    #[test]
+    fn a_function_using_several_constructs_is_used_for_the_one_left_over() {
+        let source = "
+            fn calculate_factorial_mixed(n: u64) -> u64 {
+                let mut f = 1;
+                for i in 1..=n { f *= i; }
+                loop { break f; }
+            }
+            fn calculate_factorial_for(n: u64) -> u64 { let mut f = 1; for i in 1..=n { f *= i; } f }
+        ";
+        assert_eq!(
+            chosen(source, &[Construct::Loop, Construct::For]),
+            Ok(vec![
+                (Construct::Loop, "calculate_factorial_mixed".to_string()),
+                (Construct::For, "calculate_factorial_for".to_string()),
+            ])
+        );
+    }
+
+    #[test]
+    fn backs_up_when_the_preferred_function_is_the_only_one_for_a_later_construct() {
+        // `calculate_factorial_for` is preferred for `for`, but it's also the only one using `loop`.
+        let source = "
+            fn calculate_factorial_for(n: u64) -> u64 {
+                let mut f = 1;
+                for i in 1..=n { f *= i; }
+                loop { break f; }
+            }
+            fn calculate_factorial(n: u64) -> u64 { let mut f = 1; for i in 1..=n { f *= i; } f }
+        ";
+        assert_eq!(
+            chosen(source, &[Construct::For, Construct::Loop]),
+            Ok(vec![
+                (Construct::For, "calculate_factorial".to_string()),
+                (Construct::Loop, "calculate_factorial_for".to_string()),
+            ])
+        );
+    }
+
+    #[test]
+    fn functions_named_after_their_construct_are_preferred() {
+        let source = "
+            fn calculate_factorial(n: u64) -> u64 { let mut f = 1; for i in 1..=n { f *= i; } f }
+            fn calculate_factorial_for(n: u64) -> u64 {
+                let mut f = 1;
+                for i in 1..=n { f *= i; }
+                let mut i = 0;
+                while i < 1 { i += 1; }
+                f
+            }
+            fn calculate_factorial_while(n: u64) -> u64 { let mut f = 1; let mut i = 1; while i <= n { f *= i; i += 1; } f }
+            fn calculate_factorial_loop(n: u64) -> u64 { let mut f = 1; let mut i = 1; loop { if i > n { break f; } f *= i; i += 1; } }
+        ";
+        assert_eq!(
+            chosen(source, ALL),
+            Ok(vec![
+                (Construct::Loop, "calculate_factorial_loop".to_string()),
+                (Construct::While, "calculate_factorial_while".to_string()),
+                (Construct::For, "calculate_factorial_for".to_string()),
+            ])
+        );
+    }
+
+    #[test]
+    fn only_functions_with_the_prefix_count() {
+        let file = syn::parse_file(
+            "fn factorial() { loop {} } fn calculate_factorial_x() { while true {} }",
+        )
+        .unwrap();
+        let implementations = implementations(&file, "calculate_factorial");
+        assert_eq!(implementations.len(), 1);
+        assert_eq!(implementations[0].name, "calculate_factorial_x");
+        assert_eq!(implementations[0].uses, [Construct::While]);
+    }
+}
@@ -0,0 +1,221 @@
+//! Checks a learner's challenge solution.
+//!
+//! A challenge's checks live in `checks/<challenge>/` inside its lesson. Every file that is
+//! present there adds a stage, and the challenge passes when all of its stages pass:
+//!
+//! - `expected.txt`: exactly what the solved challenge should print
+//! - `tests.rs`: hidden tests that call the functions the challenge asks for
+//! - `properties.rs`: random inputs compared against the solution in `reference.rs`
+//! - `loops.toml`: a function for every loop construct the challenge asks to use, giving the
+//!   same results
+//!
+//! Challenges that turn points into grades also have a `schemes.toml`, with the grading scheme
+//! the other files are checked against.
+
+mod loops;
+mod output;
+mod properties;
+mod scheme;
+mod unit;
+
+use std::process::ExitStatus;
+
+use crate::diagnostics::Diagnostic;
+use crate::diff;
+use crate::error::{Error, Result};
+use crate::exec::{Exceeded, Limits};
+use crate::explain::Guide;
+use crate::harness::{TestResult, TestRun};
+use crate::lesson::Lesson;
+use scheme::Scheme;
+
+pub enum Outcome {
+    Passed,
+    /// The challenge didn't compile.
+    BuildFailed(Vec<Diagnostic>),
+    /// It compiled, but didn't exit successfully (e.g. it panicked).
+    Crashed {
+        status: ExitStatus,
+        stderr: String,
+    },
+    /// It was stopped for running too long, printing too much or using too much memory.
+    Exceeded(Exceeded),
+    /// It ran, but printed something else. Holds the expected and the actual output.
+    Mismatch {
+        expected: String,
+        actual: String,
+    },
+    /// Some of the hidden tests failed. Holds every test, passed or not.
+    TestsFailed(Vec<TestResult>),
+    /// It doesn't have what the challenge asks for, e.g. a function using `while`. Holds what's
+    /// missing.
+    Incomplete(Vec<String>),
+}
+
+impl Outcome {
+    pub fn passed(&self) -> bool {
+        matches!(self, Outcome::Passed)
+    }
+
+    /// The outcome of a stage that runs tests in the hidden harness: it passes when every test
+    /// does.
+    fn of_tests(run: TestRun) -> Outcome {
+        match run {
+            TestRun::BuildFailed(errors) => Outcome::BuildFailed(errors),
+            TestRun::Ran(results) if results.iter().all(|result| result.passed) => Outcome::Passed,
+            TestRun::Ran(results) => Outcome::TestsFailed(results),
+        }
+    }
+}
+
+/// One kind of check, e.g. comparing the output.
+pub struct Stage {
+    pub name: &'static str,
+    pub outcome: Outcome,
+}
+
+pub struct Report {
+    pub stages: Vec<Stage>,
+    /// The grading scheme the challenge was checked against, if it's graded on one.
+    pub scheme: Option<Scheme>,
+}
+
+impl Report {
+    pub fn passed(&self) -> bool {
+        self.stages.iter().all(|stage| stage.outcome.passed())
+    }
+}
+
+pub fn check(lesson: &Lesson, challenge: &str, limits: &Limits) -> Result<Report> {
+    let challenge = lesson.challenge(challenge)?;
+
+    let scheme = scheme::load(lesson, challenge)?;
+    let given = match &scheme {
+        Some(scheme) => vec![scheme.module(lesson, challenge)?],
+        None => Vec::new(),
+    };
+
+    let mut stages = Vec::new();
+    if let Some(outcome) = output::check(lesson, challenge, scheme.as_ref(), limits)? {
+        stages.push(Stage {
+            name: "output",
+            outcome,
+        });
+    }
+    if let Some(outcome) = unit::check(lesson, challenge, &given, limits)? {
+        stages.push(Stage {
+            name: "tests",
+            outcome,
+        });
+    }
+
+    if let Some(outcome) = properties::check(lesson, challenge, &given, limits)? {
+        stages.push(Stage {
+            name: "properties",
+            outcome,
+        });
+    }
+
+    if let Some(outcome) = loops::check(lesson, challenge, limits)? {
+        stages.push(Stage {
+            name: "loops",
+            outcome,
+        });
+    }
+
+    if stages.is_empty() {
+        return Err(Error::NoChecks {
+            lesson: lesson.id(),
+            challenge: challenge.to_string(),
+        });
+    }
+
+    Ok(Report { stages, scheme })
+}
+
+pub fn print(lesson: &Lesson, challenge: &str, report: &Report, guide: &Guide) {
+    let status = if report.passed() { "PASS" } else { "FAIL" };
+    let stages: Vec<&str> = report.stages.iter().map(|stage| stage.name).collect();
+    println!(
+        "{} {} {} ({})",
+        status,
+        lesson.id(),
+        challenge,
+        stages.join(", ")
+    );
+
+    for stage in &report.stages {
+        print_outcome(stage.name, &stage.outcome, guide);
+    }
+    if let Some(scheme) = report.scheme.as_ref().filter(|_| !report.passed()) {
+        println!(
+            "  graded on the `{}` scheme ({}): {}",
+            scheme.name,
+            scheme.description,
+            scheme.summary()
+        );
+    }
+}
+
+fn print_outcome(stage: &str, outcome: &Outcome, guide: &Guide) {
+    match outcome {
+        Outcome::Passed => {}
+        Outcome::BuildFailed(errors) if missing_functions(errors).is_some() => {
+            let missing = missing_functions(errors).unwrap_or_default();
+            println!(
+                "  {}: the challenge doesn't define `{}` yet",
+                stage,
+                missing.join("`, `")
+            );
+        }
+        Outcome::BuildFailed(errors) => {
+            println!("  {}: it doesn't compile", stage);
+            guide.print_errors(errors);
+        }
+        Outcome::Crashed { status, stderr } => {
+            println!("  {}: it exited with {}", stage, status);
+            print!("{}", stderr);
+        }
+        Outcome::Exceeded(exceeded) => println!("  {}: it {}", stage, exceeded),
+        Outcome::Mismatch { expected, actual } => {
+            println!(
+                "  {}: the output doesn't match (- expected, + actual)",
+                stage
+            );
+            diff::print(&diff::lines(expected, actual), "    ");
+        }
+        Outcome::Incomplete(missing) => {
+            for problem in missing {
+                println!("  {}: {}", stage, problem);
+            }
+        }
+        Outcome::TestsFailed(results) => {
+            let failed: Vec<&TestResult> = results.iter().filter(|result| !result.passed).collect();
+            println!("  {}: {} of {} failed", stage, failed.len(), results.len());
+            for result in failed {
+                println!("    ✗ {}", result.name);
+                for line in result.message.lines() {
+                    println!("        {}", line);
+                }
+            }
+        }
+    }
+}
+
+/// The functions the hidden checks call, but the learner hasn't written yet.
+/// Returns `None` if anything else is wrong, so the full compiler errors get shown instead.
+fn missing_functions(errors: &[Diagnostic]) -> Option<Vec<&str>> {
+    let mut missing = Vec::new();
+    for error in errors {
+        let name = error
+            .message
+            .strip_prefix("cannot find function `")
+            .and_then(|rest| rest.split_once('`'))
+            .map(|(name, _)| name)?;
+        if !missing.contains(&name) {
+            missing.push(name);
+        }
+    }
+
+    (!missing.is_empty()).then_some(missing)
+}
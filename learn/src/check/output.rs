@@ -0,0 +1,60 @@
+//! Compares what a challenge prints with `checks/<challenge>/expected.txt`.
+
+use std::fs;
+
+use super::scheme::Scheme;
+use super::Outcome;
+use crate::error::{Error, Result};
+use crate::exec::Limits;
+use crate::lesson::Lesson;
+use crate::{diagnostics, diff, exec};
+
+pub const SPEC: &str = "expected.txt";
+
+/// Builds and runs the challenge, then compares what it printed with its spec, with the grades
+/// filled in from the scheme in use. Returns `None` when the challenge has no expected output to
+/// compare against.
+pub fn check(
+    lesson: &Lesson,
+    challenge: &str,
+    scheme: Option<&Scheme>,
+    limits: &Limits,
+) -> Result<Option<Outcome>> {
+    let spec = lesson.checks_dir(challenge).join(SPEC);
+    if !spec.is_file() {
+        return Ok(None);
+    }
+    let mut expected = fs::read_to_string(&spec).map_err(|e| Error::io(&spec, e))?;
+    if let Some(scheme) = scheme {
+        expected = scheme.fill(&expected);
+    }
+
+    let build = diagnostics::build(lesson, Some(challenge), false)?;
+    let executable = match build.executable {
+        Some(executable) if build.success => executable,
+        _ => {
+            return Ok(Some(Outcome::BuildFailed(
+                build.errors().cloned().collect(),
+            )))
+        }
+    };
+
+    let run = exec::capture(lesson, &executable, limits)?;
+    if let Some(exceeded) = run.exceeded {
+        return Ok(Some(Outcome::Exceeded(exceeded)));
+    }
+    if !run.status.success() {
+        return Ok(Some(Outcome::Crashed {
+            status: run.status,
+            stderr: run.stderr,
+        }));
+    }
+
+    let expected = diff::normalize(&expected);
+    let actual = diff::normalize(&run.stdout);
+    if expected == actual {
+        Ok(Some(Outcome::Passed))
+    } else {
+        Ok(Some(Outcome::Mismatch { expected, actual }))
+    }
+}
@@ -0,0 +1,53 @@
+//! Compares the learner's functions against a reference solution on thousands of random inputs,
+//! using `checks/<challenge>/properties.rs` and `checks/<challenge>/reference.rs`.
+
+use std::fs;
+
+use super::Outcome;
+use crate::error::{Error, Result};
+use crate::exec::Limits;
+use crate::harness::{self, Module};
+use crate::lesson::Lesson;
+
+pub const PROPERTIES: &str = "properties.rs";
+pub const REFERENCE: &str = "reference.rs";
+
+/// The property testing engine the properties are written against, available to them as `prop`.
+const ENGINE: &str = include_str!("../../support/prop.rs");
+
+/// Returns `None` when the challenge has no properties. `given` are compiled next to the
+/// reference and the properties, e.g. the grading scheme in use.
+pub fn check(
+    lesson: &Lesson,
+    challenge: &str,
+    given: &[Module],
+    limits: &Limits,
+) -> Result<Option<Outcome>> {
+    let checks = lesson.checks_dir(challenge);
+    let properties = checks.join(PROPERTIES);
+    if !properties.is_file() {
+        return Ok(None);
+    }
+
+    let engine = lesson.scratch_dir(challenge).join("prop.rs");
+    fs::create_dir_all(lesson.scratch_dir(challenge)).map_err(|e| Error::io(&engine, e))?;
+    fs::write(&engine, ENGINE).map_err(|e| Error::io(&engine, e))?;
+
+    let mut modules = given.to_vec();
+    modules.extend([
+        Module {
+            name: "prop",
+            path: engine,
+        },
+        Module {
+            name: "reference",
+            path: checks.join(REFERENCE),
+        },
+        Module {
+            name: "properties",
+            path: properties,
+        },
+    ]);
+    let run = harness::run(lesson, challenge, &modules, limits)?;
+    Ok(Some(Outcome::of_tests(run)))
+}
@@ -0,0 +1,346 @@
+//! Grading schemes for challenges that turn points into grades, from `checks/<challenge>/schemes.toml`:
+//!
+//! ```toml
+//! use = "letters"
+//!
+//! [[schemes]]
+//! name = "letters"
+//! description = "Letter grades from A to F"
+//! bands = [
+//!     { from = 0, to = 59, grade = "F" },
+//!     { from = 60, to = 100, grade = "A" },
+//! ]
+//! ```
+//!
+//! The scheme named by `use` is compiled into the hidden checks as a `scheme` module, and
+//! `{grade:<points>}` in `expected.txt` stands for the grade it gives those points. The bands of
+//! every scheme must cover 0 to 100 points, each exactly once.
+
+use std::fmt::Write as _;
+use std::fs;
+
+use serde::Deserialize;
+
+use crate::error::{Error, Result};
+use crate::harness::Module;
+use crate::lesson::Lesson;
+
+pub const SCHEMES: &str = "schemes.toml";
+
+/// The points every scheme has to cover.
+const MAX_POINTS: u32 = 100;
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Schemes {
+    /// The name of the scheme to check against.
+    #[serde(rename = "use")]
+    selected: String,
+    schemes: Vec<Scheme>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Scheme {
+    pub name: String,
+    pub description: String,
+    pub bands: Vec<Band>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Band {
+    /// The lowest points in the band.
+    pub from: u32,
+    /// The highest points in the band, included.
+    pub to: u32,
+    pub grade: String,
+}
+
+/// Loads and validates the challenge's schemes, returning the one in use.
+/// Returns `None` when the challenge isn't graded on a scheme.
+pub fn load(lesson: &Lesson, challenge: &str) -> Result<Option<Scheme>> {
+    let path = lesson.checks_dir(challenge).join(SCHEMES);
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let contents = fs::read_to_string(&path).map_err(|e| Error::io(&path, e))?;
+    let schemes: Schemes = toml::from_str(&contents).map_err(|source| Error::CheckSpec {
+        path: path.clone(),
+        source,
+    })?;
+
+    let mut problems = Vec::new();
+    for (index, scheme) in schemes.schemes.iter().enumerate() {
+        if schemes.schemes[..index]
+            .iter()
+            .any(|other| other.name == scheme.name)
+        {
+            problems.push(format!("scheme `{}` is defined twice", scheme.name));
+        }
+        problems.extend(
+            scheme
+                .problems()
+                .into_iter()
+                .map(|problem| format!("scheme `{}`: {}", scheme.name, problem)),
+        );
+    }
+
+    let selected = schemes
+        .schemes
+        .into_iter()
+        .find(|scheme| scheme.name == schemes.selected);
+    if selected.is_none() {
+        problems.push(format!(
+            "`use = \"{}\"` doesn't name one of the schemes",
+            schemes.selected
+        ));
+    }
+
+    if !problems.is_empty() {
+        return Err(Error::Schemes { path, problems });
+    }
+    Ok(selected)
+}
+
+impl Scheme {
+    /// Everything that keeps the bands from covering 0 to 100 points, each exactly once.
+    pub fn problems(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        if self.bands.is_empty() {
+            problems.push("it has no bands".to_string());
+            return problems;
+        }
+
+        for band in &self.bands {
+            if band.grade.trim().is_empty() {
+                problems.push(format!("band {} has no grade", band.range()));
+            }
+            if band.from > band.to {
+                problems.push(format!("band {} ends before it starts", band.range()));
+            }
+            if band.to > MAX_POINTS {
+                problems.push(format!(
+                    "band {} goes past {} points",
+                    band.range(),
+                    MAX_POINTS
+                ));
+            }
+        }
+
+        let mut bands: Vec<&Band> = self.bands.iter().collect();
+        bands.sort_by_key(|band| (band.from, band.to));
+        // The band reaching the highest points so far, which the next one has to continue.
+        let mut reach: Option<&Band> = None;
+        for band in bands {
+            let covered = reach.map_or(0, |reach| reach.to.saturating_add(1));
+            match reach {
+                Some(reach) if band.from < covered => problems.push(format!(
+                    "bands {} ({}) and {} ({}) overlap",
+                    reach.range(),
+                    reach.grade,
+                    band.range(),
+                    band.grade
+                )),
+                _ if band.from > covered => {
+                    problems.push(format!("no band covers {}-{}", covered, band.from - 1))
+                }
+                _ => {}
+            }
+            if reach.is_none_or(|reach| band.to > reach.to) {
+                reach = Some(band);
+            }
+        }
+        if let Some(reach) = reach.filter(|reach| reach.to < MAX_POINTS) {
+            problems.push(format!("no band covers {}-{}", reach.to + 1, MAX_POINTS));
+        }
+
+        problems
+    }
+
+    /// The grade the scheme gives those points.
+    pub fn grade(&self, points: u32) -> Option<&str> {
+        self.bands
+            .iter()
+            .find(|band| (band.from..=band.to).contains(&points))
+            .map(|band| band.grade.as_str())
+    }
+
+    /// Replaces every `{grade:<points>}` in `text` with the grade for those points.
+    pub fn fill(&self, text: &str) -> String {
+        let mut filled = String::new();
+        let mut rest = text;
+        while let Some(start) = rest.find("{grade:") {
+            let after = &rest[start + "{grade:".len()..];
+            let grade = after.split_once('}').and_then(|(points, _)| {
+                let grade = self.grade(points.trim().parse().ok()?)?;
+                Some((points.len() + 1, grade))
+            });
+            match grade {
+                Some((length, grade)) => {
+                    filled.push_str(&rest[..start]);
+                    filled.push_str(grade);
+                    rest = &after[length..];
+                }
+                None => {
+                    filled.push_str(&rest[..start + 1]);
+                    rest = &rest[start + 1..];
+                }
+            }
+        }
+        filled.push_str(rest);
+        filled
+    }
+
+    /// The bands in order, e.g. `0-59 F, 60-69 D, ...`.
+    pub fn summary(&self) -> String {
+        let mut bands: Vec<&Band> = self.bands.iter().collect();
+        bands.sort_by_key(|band| band.from);
+        bands
+            .iter()
+            .map(|band| format!("{} {}", band.range(), band.grade))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Writes the scheme as Rust to the challenge's scratch directory, to be compiled next to the
+    /// hidden checks as `scheme::NAME` and `scheme::BANDS`.
+    pub fn module(&self, lesson: &Lesson, challenge: &str) -> Result<Module<'static>> {
+        let scratch = lesson.scratch_dir(challenge);
+        fs::create_dir_all(&scratch).map_err(|e| Error::io(&scratch, e))?;
+        let path = scratch.join("scheme.rs");
+        fs::write(&path, self.source()).map_err(|e| Error::io(&path, e))?;
+
+        Ok(Module {
+            name: "scheme",
+            path,
+        })
+    }
+
+    fn source(&self) -> String {
+        let mut source = String::new();
+        writeln!(source, "// Generated by `learn check` from {}.", SCHEMES).unwrap();
+        writeln!(source, "pub const NAME: &str = {:?};", self.name).unwrap();
+        writeln!(source, "pub const BANDS: &[(u32, u32, &str)] = &[").unwrap();
+        for band in &self.bands {
+            writeln!(
+                source,
+                "    ({}, {}, {:?}),",
+                band.from, band.to, band.grade
+            )
+            .unwrap();
+        }
+        writeln!(source, "];").unwrap();
+        source
+    }
+}
+
+impl Band {
+    fn range(&self) -> String {
+        format!("{}-{}", self.from, self.to)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn with_bands(bands: &[(u32, u32, &str)]) -> Scheme {
+        Scheme {
+            name: "test".to_string(),
+            description: "a scheme under test".to_string(),
+            bands: bands
+                .iter()
+                .map(|&(from, to, grade)| Band {
+                    from,
+                    to,
+                    grade: grade.to_string(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn bands_covering_0_to_100_once_have_no_problems() {
+        let scheme = with_bands(&[(60, 100, "pass"), (0, 59, "fail")]);
+        assert_eq!(scheme.problems(), Vec::<String>::new());
+    }
+
+    #[test]
+    fn a_gap_between_bands() {
+        let scheme = with_bands(&[(0, 49, "fail"), (60, 100, "pass")]);
+        assert_eq!(scheme.problems(), ["no band covers 50-59"]);
+    }
+
+    #[test]
+    fn overlapping_bands() {
+        let scheme = with_bands(&[(0, 49, "fail"), (45, 100, "pass")]);
+        assert_eq!(
+            scheme.problems(),
+            ["bands 0-49 (fail) and 45-100 (pass) overlap"]
+        );
+    }
+
+    #[test]
+    fn a_band_that_ends_before_it_starts() {
+        let scheme = with_bands(&[(0, 49, "fail"), (100, 50, "pass")]);
+        let problems = scheme.problems();
+        assert!(
+            problems.contains(&"band 100-50 ends before it starts".to_string()),
+            "{:?}",
+            problems
+        );
+    }
+
+    #[test]
+    fn the_first_band_has_to_start_at_0() {
+        let scheme = with_bands(&[(1, 100, "pass")]);
+        assert_eq!(scheme.problems(), ["no band covers 0-0"]);
+
+        let scheme = with_bands(&[(0, 0, "none"), (1, 100, "some")]);
+        assert_eq!(scheme.problems(), Vec::<String>::new());
+    }
+
+    #[test]
+    fn the_last_band_has_to_reach_100() {
+        let scheme = with_bands(&[(0, 49, "fail"), (50, 99, "pass")]);
+        assert_eq!(scheme.problems(), ["no band covers 100-100"]);
+
+        let scheme = with_bands(&[(0, 49, "fail"), (50, 101, "pass")]);
+        assert_eq!(scheme.problems(), ["band 50-101 goes past 100 points"]);
+    }
+
+    #[test]
+    fn bands_need_a_grade() {
+        assert_eq!(with_bands(&[]).problems(), ["it has no bands"]);
+        assert_eq!(
+            with_bands(&[(0, 100, " ")]).problems(),
+            ["band 0-100 has no grade"]
+        );
+    }
+
+    #[test]
+    fn fill_replaces_grades_by_their_points() {
+        let scheme = with_bands(&[(0, 49, "fail"), (50, 100, "pass")]);
+        assert_eq!(
+            scheme.fill("49 points: {grade:49}, 50 points: {grade: 50 }"),
+            "49 points: fail, 50 points: pass"
+        );
+        assert_eq!(scheme.fill("{grade:0}{grade:100}"), "failpass");
+    }
+
+    #[test]
+    fn fill_leaves_what_isnt_a_grade_alone() {
+        let scheme = with_bands(&[(0, 49, "fail"), (50, 100, "pass")]);
+        for text in [
+            "{grade:101}",
+            "{grade:abc}",
+            "{grade:50",
+            "{grade:-1} and {grade}",
+            "no placeholders",
+        ] {
+            assert_eq!(scheme.fill(text), text);
+        }
+        assert_eq!(scheme.fill("{grade:{grade:7}}"), "{grade:fail}");
+    }
+}
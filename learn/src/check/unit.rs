@@ -0,0 +1,31 @@
+//! Calls the functions a challenge asks for directly, using the hidden `checks/<challenge>/tests.rs`.
+
+use super::Outcome;
+use crate::error::Result;
+use crate::exec::Limits;
+use crate::harness::{self, Module};
+use crate::lesson::Lesson;
+
+pub const TESTS: &str = "tests.rs";
+
+/// Returns `None` when the challenge has no hidden tests. `given` are compiled next to the tests,
+/// e.g. the grading scheme in use.
+pub fn check(
+    lesson: &Lesson,
+    challenge: &str,
+    given: &[Module],
+    limits: &Limits,
+) -> Result<Option<Outcome>> {
+    let tests = lesson.checks_dir(challenge).join(TESTS);
+    if !tests.is_file() {
+        return Ok(None);
+    }
+
+    let mut modules = given.to_vec();
+    modules.push(Module {
+        name: "hidden_tests",
+        path: tests,
+    });
+    let run = harness::run(lesson, challenge, &modules, limits)?;
+    Ok(Some(Outcome::of_tests(run)))
+}
@@ -0,0 +1,102 @@
+//! Reads the prose out of the comment blocks the lessons are written in, for the tools that
+//! show parts of a lesson next to their own output.
+
+/// The rest of a `// @<tag> ...` line, or `None` if the line isn't that tag.
+pub fn tag<'a>(line: &'a str, tag: &str) -> Option<&'a str> {
+    line.trim_start()
+        .strip_prefix("//")?
+        .trim_start()
+        .strip_prefix(tag)
+}
+
+/// Whether the line is a `// @<tag> ...` comment of any kind.
+pub fn is_tag(line: &str) -> bool {
+    tag(line, "@").is_some()
+}
+
+/// Tags and blank lines can sit between a comment block and what it's about.
+fn is_gap(line: &str) -> bool {
+    line.trim().is_empty() || is_tag(line)
+}
+
+fn is_line_comment(line: &str) -> bool {
+    line.trim_start().starts_with("//") && !is_tag(line)
+}
+
+/// The comment block ending right above `index`, with blank lines and tags in between allowed.
+pub fn block_above(lines: &[&str], index: usize) -> Vec<String> {
+    let mut end = index;
+    while end > 0 && is_gap(lines[end - 1]) {
+        end -= 1;
+    }
+    let mut start = end;
+    if start > 0 && lines[start - 1].trim_end().ends_with("*/") {
+        while start > 0 {
+            start -= 1;
+            if lines[start].trim_start().starts_with("/*") {
+                break;
+            }
+        }
+    } else {
+        while start > 0 && is_line_comment(lines[start - 1]) {
+            start -= 1;
+        }
+    }
+
+    text(&lines[start..end])
+}
+
+/// The comment block starting right below `index`, with blank lines and tags in between allowed.
+pub fn block_below(lines: &[&str], index: usize) -> Vec<String> {
+    let mut start = index;
+    while start < lines.len() && is_gap(lines[start]) {
+        start += 1;
+    }
+    let mut end = start;
+    if start < lines.len() && lines[start].trim_start().starts_with("/*") {
+        while end < lines.len() {
+            end += 1;
+            if lines[end - 1].trim_end().ends_with("*/") {
+                break;
+            }
+        }
+    } else {
+        while end < lines.len() && is_line_comment(lines[end]) {
+            end += 1;
+        }
+    }
+
+    text(&lines[start..end])
+}
+
+/// Strips the comment syntax, keeping just the prose.
+pub fn text(lines: &[&str]) -> Vec<String> {
+    let text: Vec<String> = lines
+        .iter()
+        .map(|line| {
+            let line = line.trim();
+            let line = line.strip_prefix("/*").unwrap_or(line);
+            let line = line.strip_suffix("*/").unwrap_or(line);
+            let line = line
+                .strip_prefix("//")
+                .or_else(|| line.strip_prefix('*'))
+                .unwrap_or(line);
+            line.trim().to_string()
+        })
+        .collect();
+
+    let start = text.iter().position(|line| !line.is_empty());
+    let end = text.iter().rposition(|line| !line.is_empty());
+    match start.zip(end) {
+        Some((start, end)) => text[start..=end].to_vec(),
+        None => Vec::new(),
+    }
+}
+
+/// Lessons often move on to the next topic within the same block, so the first paragraph is
+/// usually all that's about the code next to it.
+pub fn first_paragraph(text: Vec<String>) -> Vec<String> {
+    text.into_iter()
+        .take_while(|line| !line.is_empty())
+        .collect()
+}
@@ -0,0 +1,23 @@
+//! Copies the lessons for a scratch run. It uses nothing else from the crate, so the integration
+//! tests include it as well instead of keeping a copy of their own.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Copies the lessons, leaving out what they've built.
+pub fn copy_dir(from: &Path, to: &Path) -> io::Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let path = entry.path();
+        if entry.file_type()?.is_dir() {
+            if entry.file_name() != "target" {
+                copy_dir(&path, &to.join(entry.file_name()))?;
+            }
+        } else {
+            fs::copy(&path, to.join(entry.file_name()))?;
+        }
+    }
+    Ok(())
+}
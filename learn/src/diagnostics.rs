@@ -0,0 +1,135 @@
+use std::path::PathBuf;
+use std::process::Command;
+
+use serde::Deserialize;
+
+use crate::cargo;
+use crate::error::{Error, Result};
+use crate::lesson::{Lesson, FIXED_FEATURE};
+
+/// One line of `cargo --message-format=json` output.
+/// Only compiler messages and the path of the built executable are kept.
+#[derive(Deserialize)]
+struct CargoMessage {
+    reason: String,
+    message: Option<Diagnostic>,
+    executable: Option<PathBuf>,
+}
+
+/// A rustc diagnostic, as emitted by `--message-format=json`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Diagnostic {
+    pub level: String,
+    pub message: String,
+    pub code: Option<Code>,
+    pub spans: Vec<Span>,
+    /// The diagnostic as rustc would print it in the terminal.
+    pub rendered: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Code {
+    pub code: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Span {
+    /// Relative to the lesson's directory, e.g. `src/main.rs`.
+    pub file_name: PathBuf,
+    pub line_start: usize,
+    pub is_primary: bool,
+}
+
+impl Diagnostic {
+    pub fn is_error(&self) -> bool {
+        self.level == "error"
+    }
+
+    /// The error code, e.g. `E0382`. Not every diagnostic has one.
+    pub fn code(&self) -> Option<&str> {
+        self.code.as_ref().map(|code| code.code.as_str())
+    }
+
+    pub fn primary_span(&self) -> Option<&Span> {
+        self.spans.iter().find(|span| span.is_primary)
+    }
+}
+
+/// The outcome of compiling a lesson.
+pub struct Build {
+    pub success: bool,
+    pub diagnostics: Vec<Diagnostic>,
+    /// The binary cargo produced, if the build got that far.
+    pub executable: Option<PathBuf>,
+}
+
+impl Build {
+    pub fn errors(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.is_error())
+    }
+}
+
+/// Type-checks the lesson's `main` and collects every diagnostic rustc reports.
+/// With `fixed`, the lesson's fixes are applied first.
+pub fn check(lesson: &Lesson, fixed: bool) -> Result<Build> {
+    let mut command = cargo::command("check", lesson);
+    command.arg("--message-format=json").arg("--quiet");
+    if fixed {
+        command.arg("--features").arg(FIXED_FEATURE);
+    }
+
+    collect(&mut command)
+}
+
+/// Builds the lesson's `main`, or one of its challenges when `example` is given,
+/// so it can be run without going through cargo. With `fixed`, the lesson's fixes are applied.
+pub fn build(lesson: &Lesson, example: Option<&str>, fixed: bool) -> Result<Build> {
+    let mut command = cargo::command("build", lesson);
+    if let Some(example) = example {
+        command.arg("--example").arg(lesson.challenge(example)?);
+    }
+    if fixed {
+        command.arg("--features").arg(FIXED_FEATURE);
+    }
+    command.arg("--message-format=json").arg("--quiet");
+
+    collect(&mut command)
+}
+
+/// Runs a cargo command with `--message-format=json` already applied and parses its output.
+pub fn collect(command: &mut Command) -> Result<Build> {
+    let output = command.output().map_err(|source| Error::Spawn {
+        program: command.get_program().to_string_lossy().into_owned(),
+        source,
+    })?;
+
+    let mut build = Build {
+        success: output.status.success(),
+        diagnostics: Vec::new(),
+        executable: None,
+    };
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    for message in stdout
+        .lines()
+        .filter_map(|line| serde_json::from_str::<CargoMessage>(line).ok())
+    {
+        match message.reason.as_str() {
+            "compiler-message" => build.diagnostics.extend(message.message),
+            "compiler-artifact" => build.executable = message.executable.or(build.executable),
+            _ => {}
+        }
+    }
+
+    Ok(build)
+}
+
+/// Parses rustc's own `--error-format=json` output, one diagnostic per line.
+pub fn parse_rustc(stderr: &str) -> Vec<Diagnostic> {
+    stderr
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
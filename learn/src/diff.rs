@@ -0,0 +1,66 @@
+//! A small line-based diff, good enough for comparing a few lines of program output.
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Line<'a> {
+    /// Present in both.
+    Same(&'a str),
+    /// Only in the expected output.
+    Missing(&'a str),
+    /// Only in the actual output.
+    Extra(&'a str),
+}
+
+/// Ignores trailing whitespace and blank lines at the end, which are invisible in a terminal anyway.
+pub fn normalize(output: &str) -> String {
+    let lines: Vec<&str> = output.lines().map(str::trim_end).collect();
+    lines.join("\n").trim_end().to_string()
+}
+
+/// Diffs `expected` against `actual` using their longest common subsequence of lines.
+pub fn lines<'a>(expected: &'a str, actual: &'a str) -> Vec<Line<'a>> {
+    let expected: Vec<&str> = expected.lines().collect();
+    let actual: Vec<&str> = actual.lines().collect();
+
+    // lcs[i][j] is the length of the LCS of expected[i..] and actual[j..].
+    let mut lcs = vec![vec![0usize; actual.len() + 1]; expected.len() + 1];
+    for i in (0..expected.len()).rev() {
+        for j in (0..actual.len()).rev() {
+            lcs[i][j] = if expected[i] == actual[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut diff = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < expected.len() && j < actual.len() {
+        if expected[i] == actual[j] {
+            diff.push(Line::Same(expected[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            diff.push(Line::Missing(expected[i]));
+            i += 1;
+        } else {
+            diff.push(Line::Extra(actual[j]));
+            j += 1;
+        }
+    }
+    diff.extend(expected[i..].iter().map(|line| Line::Missing(line)));
+    diff.extend(actual[j..].iter().map(|line| Line::Extra(line)));
+
+    diff
+}
+
+/// Prints the diff with `-` for expected lines and `+` for actual ones, indented by `indent`.
+pub fn print(diff: &[Line], indent: &str) {
+    for line in diff {
+        match line {
+            Line::Same(text) => println!("{}  {}", indent, text),
+            Line::Missing(text) => println!("{}- {}", indent, text),
+            Line::Extra(text) => println!("{}+ {}", indent, text),
+        }
+    }
+}
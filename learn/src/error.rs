@@ -0,0 +1,167 @@
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug)]
+pub enum Error {
+    /// An I/O operation on `path` failed.
+    Io { path: PathBuf, source: io::Error },
+    /// A lesson's `Cargo.toml` could not be parsed.
+    Manifest {
+        path: PathBuf,
+        source: toml::de::Error,
+    },
+    /// A lesson's `[package.metadata.lesson]` table is missing or doesn't match the lesson.
+    Metadata {
+        path: PathBuf,
+        problems: Vec<String>,
+    },
+    /// One of a challenge's TOML files in `checks/<challenge>`, e.g. `schemes.toml`, could not be
+    /// parsed.
+    CheckSpec {
+        path: PathBuf,
+        source: toml::de::Error,
+    },
+    /// A challenge's grading schemes don't cover every score exactly once, or none is in use.
+    Schemes {
+        path: PathBuf,
+        problems: Vec<String>,
+    },
+    /// The learner's progress file could not be parsed.
+    Progress {
+        path: PathBuf,
+        source: toml::de::Error,
+    },
+    /// The learner's progress could not be serialized.
+    ProgressWrite(toml::ser::Error),
+    /// The `lessons` directory could not be located.
+    RootNotFound,
+    /// No lesson matches what the user typed.
+    LessonNotFound(String),
+    /// The lesson exists, but has no challenge with that name.
+    ChallengeNotFound { lesson: String, name: String },
+    /// The challenge has nothing in `checks/<challenge>` to check it against.
+    NoChecks { lesson: String, challenge: String },
+    /// The challenge has no `checks/<challenge>/hints.md`.
+    NoHints { lesson: String, challenge: String },
+    /// The lesson has no `@try-error` snippet with that name.
+    SnippetNotFound { lesson: String, name: String },
+    /// The lesson has no snippet at that step that runs on its own.
+    StepNotFound { lesson: String, step: String },
+    /// No glossary term matches what the user typed.
+    TermNotFound(String),
+    /// `--fixed` was requested for a lesson that compiles as-is.
+    NoFixedVariant(String),
+    /// A spawned command could not be started.
+    Spawn { program: String, source: io::Error },
+    /// `learn serve` couldn't listen on the address.
+    Bind { address: String, source: io::Error },
+    /// `learn browse` was started without a terminal to draw on.
+    NotATerminal,
+    /// Drawing on or reading from the terminal failed.
+    Terminal(io::Error),
+}
+
+impl Error {
+    pub fn io(path: impl Into<PathBuf>, source: io::Error) -> Self {
+        Error::Io {
+            path: path.into(),
+            source,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io { path, source } => write!(f, "{}: {}", path.display(), source),
+            Error::Manifest { path, source } => {
+                write!(f, "invalid manifest {}: {}", path.display(), source)
+            }
+            Error::Metadata { path, problems } => {
+                write!(f, "invalid lesson metadata in {}:", path.display())?;
+                for problem in problems {
+                    write!(f, "\n  - {}", problem)?;
+                }
+                Ok(())
+            }
+            Error::CheckSpec { path, source } => {
+                write!(f, "invalid check file {}: {}", path.display(), source)
+            }
+            Error::Schemes { path, problems } => {
+                write!(f, "invalid grading schemes in {}:", path.display())?;
+                for problem in problems {
+                    write!(f, "\n  - {}", problem)?;
+                }
+                Ok(())
+            }
+            Error::Progress { path, source } => {
+                write!(f, "invalid progress file {}: {}", path.display(), source)
+            }
+            Error::ProgressWrite(source) => write!(f, "could not save progress: {}", source),
+            Error::RootNotFound => write!(
+                f,
+                "could not find the `lessons` directory; run `learn` from inside the repo"
+            ),
+            Error::LessonNotFound(query) => {
+                write!(f, "no lesson matches `{}`; try `learn list`", query)
+            }
+            Error::ChallengeNotFound { lesson, name } => {
+                write!(f, "lesson {} has no challenge named `{}`", lesson, name)
+            }
+            Error::NoChecks { lesson, challenge } => write!(
+                f,
+                "lesson {} has no checks for `{}`; add them to checks/{}",
+                lesson, challenge, challenge
+            ),
+            Error::NoHints { lesson, challenge } => {
+                write!(f, "lesson {} has no hints for `{}`", lesson, challenge)
+            }
+            Error::SnippetNotFound { lesson, name } => write!(
+                f,
+                "lesson {} has no snippet named `{}`; try `learn try {}`",
+                lesson, name, lesson
+            ),
+            Error::StepNotFound { lesson, step } => write!(
+                f,
+                "lesson {} has no snippet {} that runs on its own",
+                lesson, step
+            ),
+            Error::TermNotFound(term) => write!(
+                f,
+                "no glossary term matches `{}`; run `learn glossary` to see them all",
+                term
+            ),
+            Error::NoFixedVariant(lesson) => {
+                write!(
+                    f,
+                    "lesson {} has no `fixed` variant; it compiles as-is",
+                    lesson
+                )
+            }
+            Error::Spawn { program, source } => {
+                write!(f, "failed to start `{}`: {}", program, source)
+            }
+            Error::Bind { address, source } => {
+                write!(f, "couldn't listen on {}: {}", address, source)
+            }
+            Error::NotATerminal => write!(f, "the browser needs to run in a terminal"),
+            Error::Terminal(source) => write!(f, "terminal error: {}", source),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } | Error::Spawn { source, .. } => Some(source),
+            Error::Bind { source, .. } | Error::Terminal(source) => Some(source),
+            Error::Manifest { source, .. } | Error::Progress { source, .. } => Some(source),
+            Error::CheckSpec { source, .. } => Some(source),
+            Error::ProgressWrite(source) => Some(source),
+            _ => None,
+        }
+    }
+}
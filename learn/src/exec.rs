@@ -0,0 +1,265 @@
+//! Runs the programs the lessons and challenges are built into, under a time, output and
+//! memory limit, so an endless `loop` or a runaway solution can't hang the terminal.
+
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::path::Path;
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::diagnostics;
+use crate::error::{Error, Result};
+use crate::explain::Guide;
+use crate::lesson::Lesson;
+
+/// How often a running program is checked on.
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// The limits every program the runner starts is held to. Zero turns a limit off.
+#[derive(Debug, Clone, Copy, clap::Args)]
+pub struct Limits {
+    /// Stop programs that run longer than this many seconds
+    #[arg(long, value_name = "SECONDS", default_value_t = 5, global = true)]
+    pub timeout: u64,
+    /// Stop programs that print more than this many KiB
+    #[arg(long, value_name = "KIB", default_value_t = 1024, global = true)]
+    pub max_output: u64,
+    /// Limit how much memory programs may use, in MiB
+    #[arg(long, value_name = "MIB", default_value_t = 512, global = true)]
+    pub max_memory: u64,
+}
+
+impl Limits {
+    /// The flags that hold another `learn` process to the same limits.
+    pub fn args(&self) -> Vec<String> {
+        vec![
+            format!("--timeout={}", self.timeout),
+            format!("--max-output={}", self.max_output),
+            format!("--max-memory={}", self.max_memory),
+        ]
+    }
+}
+
+/// Which limit a program ran into.
+#[derive(Debug, Clone, Copy)]
+pub enum Exceeded {
+    Timeout(u64),
+    Output(u64),
+    Memory(u64),
+}
+
+impl fmt::Display for Exceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Exceeded::Timeout(seconds) => write!(f, "timed out after {}s", seconds),
+            Exceeded::Output(kib) => write!(f, "printed more than {} KiB of output", kib),
+            Exceeded::Memory(mib) => write!(f, "ran out of memory (limited to {} MiB)", mib),
+        }
+    }
+}
+
+/// A finished program, along with what it printed, up to the output limit.
+pub struct Run {
+    pub status: ExitStatus,
+    pub stdout: String,
+    pub stderr: String,
+    /// Set when the program was stopped, or failed, because of one of the limits.
+    pub exceeded: Option<Exceeded>,
+}
+
+impl Run {
+    pub fn success(&self) -> bool {
+        self.exceeded.is_none() && self.status.success()
+    }
+}
+
+/// Runs the lesson's program from its directory and captures what it prints.
+pub fn capture(lesson: &Lesson, executable: &Path, limits: &Limits) -> Result<Run> {
+    let mut command = Command::new(executable);
+    command.current_dir(&lesson.dir);
+    execute(command, limits, false)
+}
+
+/// Runs the program and captures what it prints, also passing it through to the terminal as it comes
+/// when `echo` is set.
+pub fn execute(mut command: Command, limits: &Limits, echo: bool) -> Result<Run> {
+    let program = command.get_program().to_string_lossy().into_owned();
+    command.stdout(Stdio::piped()).stderr(Stdio::piped());
+    if limits.max_memory > 0 {
+        limit_memory(&mut command, limits.max_memory.saturating_mul(1024 * 1024));
+    }
+    let mut child = command.spawn().map_err(|source| Error::Spawn {
+        program: program.clone(),
+        source,
+    })?;
+
+    let printed = Arc::new(AtomicU64::new(0));
+    let over_limit = Arc::new(AtomicBool::new(false));
+    let max_output = limits.max_output.saturating_mul(1024);
+    let stdout = child.stdout.take().map(|pipe| {
+        let (printed, over_limit) = (Arc::clone(&printed), Arc::clone(&over_limit));
+        thread::spawn(move || {
+            let echo = echo.then(io::stdout);
+            read(pipe, echo, &printed, max_output, &over_limit)
+        })
+    });
+    let stderr = child.stderr.take().map(|pipe| {
+        let (printed, over_limit) = (Arc::clone(&printed), Arc::clone(&over_limit));
+        thread::spawn(move || {
+            let echo = echo.then(io::stderr);
+            read(pipe, echo, &printed, max_output, &over_limit)
+        })
+    });
+
+    let (status, mut exceeded) =
+        wait(&mut child, limits, &over_limit).map_err(|source| Error::Spawn { program, source })?;
+    let stdout = stdout.map(join).unwrap_or_default();
+    let stderr = stderr.map(join).unwrap_or_default();
+
+    // Rust aborts with this message when an allocation fails, which is what hitting the limit looks like.
+    if exceeded.is_none()
+        && limits.max_memory > 0
+        && !status.success()
+        && stderr.contains("memory allocation of")
+    {
+        exceeded = Some(Exceeded::Memory(limits.max_memory));
+    }
+
+    Ok(Run {
+        status,
+        stdout,
+        stderr,
+        exceeded,
+    })
+}
+
+/// Waits for the child to exit, killing it if it runs out of time or prints too much.
+fn wait(
+    child: &mut Child,
+    limits: &Limits,
+    over_limit: &AtomicBool,
+) -> io::Result<(ExitStatus, Option<Exceeded>)> {
+    let started = Instant::now();
+    let timeout = Duration::from_secs(limits.timeout);
+
+    loop {
+        if let Some(status) = child.try_wait()? {
+            // Once its output is cut off, the program usually dies of the broken pipe on its own.
+            let exceeded = over_limit
+                .load(Ordering::Relaxed)
+                .then_some(Exceeded::Output(limits.max_output));
+            return Ok((status, exceeded));
+        }
+
+        let exceeded = if over_limit.load(Ordering::Relaxed) {
+            Some(Exceeded::Output(limits.max_output))
+        } else if limits.timeout > 0 && started.elapsed() >= timeout {
+            Some(Exceeded::Timeout(limits.timeout))
+        } else {
+            None
+        };
+        if exceeded.is_some() {
+            // It may have exited in the meantime, which is fine.
+            let _ = child.kill();
+            let status = child.wait()?;
+            return Ok((status, exceeded));
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Reads a pipe until it closes, keeping at most `max_output` bytes across all pipes
+/// (zero for no limit) and raising `over_limit` once there's more.
+fn read(
+    mut pipe: impl Read,
+    mut echo: Option<impl Write>,
+    printed: &AtomicU64,
+    max_output: u64,
+    over_limit: &AtomicBool,
+) -> Vec<u8> {
+    let mut kept = Vec::new();
+    let mut buffer = [0; 8192];
+
+    while let Ok(read @ 1..) = pipe.read(&mut buffer) {
+        let total = printed.fetch_add(read as u64, Ordering::Relaxed) + read as u64;
+        let allowed = if max_output > 0 && total > max_output {
+            read - (total - max_output).min(read as u64) as usize
+        } else {
+            read
+        };
+
+        kept.extend_from_slice(&buffer[..allowed]);
+        if let Some(echo) = echo.as_mut() {
+            let _ = echo.write_all(&buffer[..allowed]);
+            let _ = echo.flush();
+        }
+        if allowed < read {
+            over_limit.store(true, Ordering::Relaxed);
+            break;
+        }
+    }
+
+    kept
+}
+
+fn join(reader: thread::JoinHandle<Vec<u8>>) -> String {
+    let bytes = reader.join().unwrap_or_default();
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+/// Caps the child's address space, so a runaway allocation fails instead of swapping the machine to death.
+#[cfg(unix)]
+fn limit_memory(command: &mut Command, bytes: u64) {
+    use std::os::unix::process::CommandExt;
+
+    let limit = libc::rlimit {
+        rlim_cur: bytes as libc::rlim_t,
+        rlim_max: bytes as libc::rlim_t,
+    };
+    // SAFETY: setrlimit is async-signal-safe and only touches the child's own limits.
+    unsafe {
+        command.pre_exec(move || {
+            if libc::setrlimit(libc::RLIMIT_AS, &limit) == 0 {
+                Ok(())
+            } else {
+                Err(io::Error::last_os_error())
+            }
+        });
+    }
+}
+
+#[cfg(not(unix))]
+fn limit_memory(_command: &mut Command, _bytes: u64) {}
+
+/// Builds and runs the lesson's `main` or one of its challenges, showing the compiler's errors
+/// if it doesn't build (and where the lessons explain them), and which limit it ran into if any.
+/// Passes when the program builds and exits successfully within the limits.
+pub fn build_and_run(
+    lesson: &Lesson,
+    example: Option<&str>,
+    fixed: bool,
+    limits: &Limits,
+    guide: &Guide,
+) -> Result<bool> {
+    let build = diagnostics::build(lesson, example, fixed)?;
+    let executable = match build.executable {
+        Some(executable) if build.success => executable,
+        _ => {
+            guide.print_errors(&build.errors().cloned().collect::<Vec<_>>());
+            return Ok(false);
+        }
+    };
+
+    let mut command = Command::new(&executable);
+    command.current_dir(&lesson.dir);
+    let run = execute(command, limits, true)?;
+    if let Some(exceeded) = run.exceeded {
+        println!("\nerror: the program {}", exceeded);
+    }
+
+    Ok(run.success())
+}
@@ -0,0 +1,216 @@
+//! Verifies that lessons which intentionally don't compile still fail for the right reason.
+//!
+//! A lesson marks every line that should fail with a `~ERROR <code>` comment:
+//!
+//! ```text
+//! println!("{}", m); // ~ERROR E0382
+//! ```
+//!
+//! The lesson passes when rustc reports exactly those error codes on exactly those lines.
+//! Lessons without any markers are expected to compile, and the `compiles` flag in a lesson's
+//! metadata has to agree.
+//!
+//! Lessons with a `fixed` feature are also built with it enabled, and must then compile cleanly.
+
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::diagnostics::{self, Diagnostic};
+use crate::error::{Error, Result};
+use crate::lesson::Lesson;
+
+const MARKER: &str = "~ERROR";
+
+/// An error rustc is expected to (or did) report.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Expected {
+    pub file: PathBuf,
+    pub line: usize,
+    pub code: String,
+}
+
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{} {}", self.file.display(), self.line, self.code)
+    }
+}
+
+pub struct Report {
+    pub expected: Vec<Expected>,
+    /// Markers rustc didn't report.
+    pub missing: Vec<Expected>,
+    /// Errors rustc reported without a marker.
+    pub unexpected: Vec<Diagnostic>,
+    /// Errors in the `fixed` variant, or `None` when the lesson doesn't have one.
+    pub fixed: Option<Vec<Diagnostic>>,
+    /// Set when the lesson's metadata says it compiles but it has markers, or the other way around.
+    pub metadata_mismatch: Option<&'static str>,
+}
+
+impl Report {
+    pub fn passed(&self) -> bool {
+        self.missing.is_empty()
+            && self.unexpected.is_empty()
+            && self.metadata_mismatch.is_none()
+            && self.fixed.as_ref().is_none_or(|errors| errors.is_empty())
+    }
+}
+
+/// Collects the `~ERROR` markers from every source file in the lesson's `src` directory.
+pub fn markers(lesson: &Lesson) -> Result<Vec<Expected>> {
+    let mut expected = Vec::new();
+    for file in sources(&lesson.dir.join("src"))? {
+        let contents = fs::read_to_string(&file).map_err(|e| Error::io(&file, e))?;
+        let relative = file
+            .strip_prefix(&lesson.dir)
+            .unwrap_or(&file)
+            .to_path_buf();
+        expected.extend(parse_markers(&relative, &contents));
+    }
+    expected.sort();
+
+    Ok(expected)
+}
+
+fn parse_markers(file: &Path, contents: &str) -> Vec<Expected> {
+    let mut expected = Vec::new();
+
+    for (index, line) in contents.lines().enumerate() {
+        let Some((_, comment)) = line.split_once("//") else {
+            continue;
+        };
+
+        for (start, _) in comment.match_indices(MARKER) {
+            let code: String = comment[start + MARKER.len()..]
+                .trim_start()
+                .chars()
+                .take_while(|c| c.is_ascii_alphanumeric())
+                .collect();
+            if !code.is_empty() {
+                expected.push(Expected {
+                    file: file.to_path_buf(),
+                    line: index + 1,
+                    code,
+                });
+            }
+        }
+    }
+
+    expected
+}
+
+/// Every `.rs` file under `dir`, sorted.
+pub fn sources(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    if !dir.is_dir() {
+        return Ok(files);
+    }
+
+    for entry in fs::read_dir(dir).map_err(|e| Error::io(dir, e))? {
+        let path = entry.map_err(|e| Error::io(dir, e))?.path();
+        if path.is_dir() {
+            files.extend(sources(&path)?);
+        } else if path.extension().is_some_and(|ext| ext == "rs") {
+            files.push(path);
+        }
+    }
+    files.sort();
+
+    Ok(files)
+}
+
+/// Compiles the lesson and compares rustc's errors against its markers.
+pub fn verify(lesson: &Lesson) -> Result<Report> {
+    let expected = markers(lesson)?;
+    let build = diagnostics::check(lesson, false)?;
+
+    let mut missing = expected.clone();
+    let mut unexpected = Vec::new();
+
+    for error in build.errors() {
+        let reported = error
+            .primary_span()
+            .zip(error.code())
+            .map(|(span, code)| Expected {
+                file: span.file_name.clone(),
+                line: span.line_start,
+                code: code.to_string(),
+            });
+
+        match reported.and_then(|reported| missing.iter().position(|e| *e == reported)) {
+            Some(index) => {
+                missing.remove(index);
+            }
+            None => unexpected.push(error.clone()),
+        }
+    }
+
+    let fixed = if lesson.has_fixed_variant {
+        let build = diagnostics::check(lesson, true)?;
+        Some(build.errors().cloned().collect())
+    } else {
+        None
+    };
+
+    let metadata_mismatch = match (lesson.metadata.compiles, expected.is_empty()) {
+        (true, false) => Some("the metadata says it compiles, but it has ~ERROR markers"),
+        (false, true) => Some("the metadata says it doesn't compile, but it has no ~ERROR markers"),
+        _ => None,
+    };
+
+    Ok(Report {
+        expected,
+        missing,
+        unexpected,
+        fixed,
+        metadata_mismatch,
+    })
+}
+
+/// Prints a one-line summary per lesson, followed by the details of any mismatch.
+pub fn print(lesson: &Lesson, report: &Report) {
+    let status = if report.passed() { "ok" } else { "FAILED" };
+    let mut summary = match report.expected.len() {
+        0 => "compiles".to_string(),
+        1 => "1 expected error".to_string(),
+        n => format!("{} expected errors", n),
+    };
+    if report.fixed.is_some() {
+        summary.push_str(", fixed variant compiles");
+    }
+    println!(
+        "{:6} {}  {} ({})",
+        status,
+        lesson.id(),
+        lesson.name,
+        summary
+    );
+
+    if let Some(mismatch) = report.metadata_mismatch {
+        println!("         {}", mismatch);
+    }
+    for missing in &report.missing {
+        println!("         missing: {}", missing);
+    }
+    for error in &report.unexpected {
+        println!("         unexpected: {}", describe(error));
+    }
+    for error in report.fixed.iter().flatten() {
+        println!("         fixed variant: {}", describe(error));
+    }
+}
+
+fn describe(error: &Diagnostic) -> String {
+    let location = error
+        .primary_span()
+        .map(|span| format!("{}:{}", span.file_name.display(), span.line_start))
+        .unwrap_or_else(|| "?".to_string());
+
+    format!(
+        "{} {} {}",
+        location,
+        error.code().unwrap_or("(no code)"),
+        error.message
+    )
+}
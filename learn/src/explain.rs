@@ -0,0 +1,126 @@
+//! Points learners from a compiler error to the part of a lesson that explains it.
+//!
+//! A lesson marks a comment block that explains an error with a tag right above it, naming the
+//! error code and the topic:
+//!
+//! ```text
+//! // @explains E0499 mutable references
+//! /*
+//!  * But, mutable references have one big limitation: ...
+//!  */
+//! ```
+//!
+//! Whenever a build fails, the runner looks up the codes of its errors and prints
+//! "See lesson 06, section on mutable references" with the first paragraph of that block.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::PathBuf;
+
+use crate::comments;
+use crate::diagnostics::Diagnostic;
+use crate::error::{Error, Result};
+use crate::expect;
+use crate::lesson::Lesson;
+
+const TAG: &str = "@explains";
+
+/// The longest excerpt shown; the rest can be read in the lesson itself.
+const MAX_EXCERPT_LINES: usize = 8;
+
+pub struct Section {
+    /// The lesson's id, e.g. `06`.
+    pub lesson: String,
+    pub code: String,
+    /// What the section is about, e.g. `mutable references`.
+    pub topic: String,
+    /// Relative to the repo root, e.g. `lessons/06-references-and-borrowing/src/main.rs`.
+    pub file: PathBuf,
+    /// The 1-based line of the tag.
+    pub line: usize,
+    pub excerpt: Vec<String>,
+}
+
+/// Every `@explains` section across the lessons, in course order.
+pub struct Guide {
+    sections: Vec<Section>,
+}
+
+impl Guide {
+    pub fn load(lessons: &[Lesson]) -> Result<Self> {
+        let mut sections = Vec::new();
+        for lesson in lessons {
+            for file in expect::sources(&lesson.dir.join("src"))? {
+                let contents = fs::read_to_string(&file).map_err(|e| Error::io(&file, e))?;
+                let lines: Vec<&str> = contents.lines().collect();
+                let relative = lesson
+                    .dir
+                    .parent()
+                    .and_then(|lessons| lessons.parent())
+                    .and_then(|root| file.strip_prefix(root).ok())
+                    .unwrap_or(&file)
+                    .to_path_buf();
+
+                for (index, line) in lines.iter().enumerate() {
+                    let Some(rest) = comments::tag(line, TAG) else {
+                        continue;
+                    };
+                    let Some((code, topic)) = rest.trim().split_once(' ') else {
+                        continue;
+                    };
+                    sections.push(Section {
+                        lesson: lesson.id(),
+                        code: code.to_string(),
+                        topic: topic.trim().to_string(),
+                        file: relative.clone(),
+                        line: index + 1,
+                        excerpt: comments::first_paragraph(comments::block_below(
+                            &lines,
+                            index + 1,
+                        )),
+                    });
+                }
+            }
+        }
+
+        Ok(Guide { sections })
+    }
+
+    /// The sections that explain `code`, e.g. `E0502`.
+    fn sections<'a>(&'a self, code: &'a str) -> impl Iterator<Item = &'a Section> {
+        self.sections
+            .iter()
+            .filter(move |section| section.code == code)
+    }
+
+    /// Prints the errors the way rustc renders them, followed by where to read up on each of them.
+    pub fn print_errors(&self, errors: &[Diagnostic]) {
+        for error in errors {
+            if let Some(rendered) = &error.rendered {
+                print!("{}", rendered);
+            }
+        }
+        self.print_sections(errors);
+    }
+
+    /// Prints where to read up on each of the errors, once per section.
+    fn print_sections(&self, errors: &[Diagnostic]) {
+        let codes: BTreeSet<&str> = errors.iter().filter_map(Diagnostic::code).collect();
+        for section in codes.into_iter().flat_map(|code| self.sections(code)) {
+            println!(
+                "\n{}: see lesson {}, section on {} ({}:{})",
+                section.code,
+                section.lesson,
+                section.topic,
+                section.file.display(),
+                section.line
+            );
+            for line in section.excerpt.iter().take(MAX_EXCERPT_LINES) {
+                println!("{}", format!("  > {}", line).trim_end());
+            }
+            if section.excerpt.len() > MAX_EXCERPT_LINES {
+                println!("  > ...");
+            }
+        }
+    }
+}
@@ -0,0 +1,724 @@
+//! The terms the lessons introduce, where each one is defined, and what the lesson says about it.
+//!
+//! Terms are picked out of the lessons' comments by the way the prose introduces them: what
+//! something "is called" or "known as", what "we call" something in quotes, and names in Title
+//! Case after "the". An abbreviation right after the term, as in "is called Non-Lexical Lifetimes
+//! (NLL for short)" or "last in, first out, or LIFO", is kept with it, and either name can then be
+//! looked up and is checked. A term is defined where it first comes up.
+//!
+//! When the wording doesn't give a term away, a tag right above the comment block that introduces
+//! it adds it, with an abbreviation in parentheses if it has one:
+//!
+//! ```text
+//! // @defines String Slices
+//! /*
+//!  * We can achieve that synchronization using string slices! A string slice is a reference to a part
+//!  * of a string.
+//!  */
+//! ```
+//!
+//! The definition itself is taken from the block: the sentence that mentions the term, with the
+//! sentence before it when it only says "It's called ..." and the one after it. `learn glossary
+//! --check` makes sure no lesson uses a term before the lesson that defines it.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::comments;
+use crate::error::{Error, Result};
+use crate::expect;
+use crate::lesson::Lesson;
+use crate::search::{self, Kind};
+
+const TAG: &str = "@defines";
+
+/// Words right before "called" that name a piece of code rather than a term, as in "a function
+/// called inner_function".
+const CODE: &[&str] = &[
+    "function", "method", "macro", "variable", "type", "crate", "module",
+];
+
+/// Words right after "called" that mean something is being called rather than named, as in "the
+/// function is called with a number".
+const CALLING: &[&str] = &[
+    "with", "by", "from", "on", "in", "for", "to", "when", "once", "at",
+];
+
+/// Words dropped from the start of a term, as in "a variable that's called its owner".
+const DETERMINERS: &[&str] = &["a", "an", "the", "its", "their"];
+
+/// The longest term, in words, that the prose is searched for.
+const MAX_WORDS: usize = 4;
+
+/// Sentences starting with these refer back to the one before, which usually holds the meaning.
+const REFERRING: &[&str] = &["it", "it's", "this", "that", "these", "those", "they"];
+
+/// Where the glossary wraps definitions.
+const WIDTH: usize = 96;
+
+pub struct Entry {
+    pub term: String,
+    /// E.g. `NLL` for Non-Lexical Lifetimes.
+    pub abbreviation: Option<String>,
+    /// Position of the defining lesson in the course.
+    lesson: usize,
+    /// Relative to the repo root, e.g. `lessons/08-structs/src/main.rs`.
+    pub file: PathBuf,
+    /// The 1-based line where the block first mentions the term, or of the tag if it never does.
+    pub line: usize,
+    /// Empty when the block never mentions the term.
+    pub definition: String,
+}
+
+impl Entry {
+    /// The term, with its abbreviation if it has one, e.g. `Non-Lexical Lifetimes (NLL)`.
+    pub fn title(&self) -> String {
+        match &self.abbreviation {
+            Some(abbreviation) => format!("{} ({})", self.term, abbreviation),
+            None => self.term.clone(),
+        }
+    }
+}
+
+/// A comment line mentioning a term.
+pub struct Mention {
+    lesson: usize,
+    pub file: PathBuf,
+    pub line: usize,
+}
+
+/// Every term across the lessons, in course order.
+pub struct Glossary<'a> {
+    lessons: &'a [Lesson],
+    entries: Vec<Entry>,
+    /// Tags that don't name a term, as problems.
+    rejected: Vec<String>,
+}
+
+impl<'a> Glossary<'a> {
+    pub fn load(lessons: &'a [Lesson]) -> Result<Self> {
+        let mut entries = Vec::new();
+        let mut rejected = Vec::new();
+        for (position, lesson) in lessons.iter().enumerate() {
+            for file in sources(lesson)? {
+                let contents = fs::read_to_string(&file).map_err(|e| Error::io(&file, e))?;
+                let lines: Vec<&str> = contents.lines().collect();
+                let passages = search::split(&contents);
+
+                // The terms the file introduces, each with the line its block comes after.
+                let mut found = Vec::new();
+                for (index, line) in lines.iter().enumerate() {
+                    let Some(tagged) = comments::tag(line, TAG) else {
+                        continue;
+                    };
+                    match parse_term(tagged) {
+                        Some((term, abbreviation)) => {
+                            found.push((index + 1, term, abbreviation, true))
+                        }
+                        None => rejected.push(format!(
+                            "{}:{}: `{}` doesn't name a term",
+                            relative(lesson, &file).display(),
+                            index + 1,
+                            line.trim().trim_start_matches("//").trim()
+                        )),
+                    }
+                }
+                for (kind, first, lines) in &passages {
+                    if *kind == Kind::Prose {
+                        for (term, abbreviation) in introduced(&lines.join(" ")) {
+                            found.push((first - 1, term, abbreviation, false));
+                        }
+                    }
+                }
+                found.sort_by_key(|(after, ..)| *after);
+
+                for (after, term, abbreviation, tagged) in found {
+                    let (line, definition) =
+                        define(&term, &passages, after).unwrap_or((after, String::new()));
+                    let file = relative(lesson, &file);
+                    let known = entries.iter().find(|entry: &&Entry| {
+                        Self::names(entry).any(|name| stems(name) == stems(&term))
+                    });
+                    // A tag can name a term its block also introduces in so many words, and a term
+                    // found in the prose is only defined where it first comes up.
+                    if known
+                        .is_some_and(|known| !tagged || (known.file == file && known.line == line))
+                    {
+                        continue;
+                    }
+                    entries.push(Entry {
+                        term,
+                        abbreviation,
+                        lesson: position,
+                        file,
+                        line,
+                        definition,
+                    });
+                }
+            }
+        }
+
+        Ok(Glossary {
+            lessons,
+            entries,
+            rejected,
+        })
+    }
+
+    pub fn lesson(&self, entry: &Entry) -> &'a Lesson {
+        &self.lessons[entry.lesson]
+    }
+
+    /// The entry's term, followed by its abbreviation if it has one.
+    fn names(entry: &Entry) -> impl Iterator<Item = &str> {
+        std::iter::once(entry.term.as_str()).chain(entry.abbreviation.as_deref())
+    }
+
+    /// The entries whose term is `query`, or else those that contain it.
+    pub fn find(&self, query: &str) -> Result<Vec<&Entry>> {
+        let query = search::words(query);
+        let exact: Vec<&Entry> = self
+            .entries
+            .iter()
+            .filter(|entry| Self::names(entry).any(|name| stems(name) == stems(&query.join(" "))))
+            .collect();
+        if !exact.is_empty() {
+            return Ok(exact);
+        }
+
+        let partial: Vec<&Entry> = self
+            .entries
+            .iter()
+            .filter(|entry| contains(&search::words(&entry.term), &query))
+            .collect();
+        if partial.is_empty() {
+            return Err(Error::TermNotFound(query.join(" ")));
+        }
+
+        Ok(partial)
+    }
+
+    /// Every comment line across the lessons that mentions the entry by any of its names, in
+    /// course order.
+    pub fn mentions(&self, entry: &Entry) -> Result<Vec<Mention>> {
+        let mut mentions = Vec::new();
+        for name in Self::names(entry) {
+            mentions.extend(self.mentions_of(name)?);
+        }
+        mentions.sort_by(|a, b| (a.lesson, &a.file, a.line).cmp(&(b.lesson, &b.file, b.line)));
+        mentions.dedup_by(|a, b| (a.lesson, &a.file, a.line) == (b.lesson, &b.file, b.line));
+
+        Ok(mentions)
+    }
+
+    fn mentions_of(&self, term: &str) -> Result<Vec<Mention>> {
+        let term = stems(term);
+        let mut mentions = Vec::new();
+        if term.is_empty() {
+            return Ok(mentions);
+        }
+        for (position, lesson) in self.lessons.iter().enumerate() {
+            for file in sources(lesson)? {
+                let contents = fs::read_to_string(&file).map_err(|e| Error::io(&file, e))?;
+                for (kind, first, lines) in search::split(&contents) {
+                    if kind == Kind::Code {
+                        continue;
+                    }
+                    // Terms like "Non-Lexical Lifetimes" can be broken across lines.
+                    let words: Vec<(usize, String)> = lines
+                        .iter()
+                        .enumerate()
+                        .flat_map(|(offset, line)| {
+                            stems(line)
+                                .into_iter()
+                                .map(move |word| (first + offset, word))
+                        })
+                        .collect();
+                    let mut previous = None;
+                    for window in words.windows(term.len()) {
+                        let line = window[0].0;
+                        if previous != Some(line)
+                            && window
+                                .iter()
+                                .zip(&term)
+                                .all(|((_, word), term)| word == term)
+                        {
+                            mentions.push(Mention {
+                                lesson: position,
+                                file: relative(lesson, &file),
+                                line,
+                            });
+                            previous = Some(line);
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(mentions)
+    }
+
+    /// What's wrong with the glossary: tags without a term, terms defined twice, definitions that
+    /// never mention their term, and terms used before the lesson that defines them.
+    pub fn problems(&self) -> Result<Vec<String>> {
+        let mut problems = self.rejected.clone();
+        let mut seen: BTreeMap<Vec<String>, &Entry> = BTreeMap::new();
+
+        for entry in &self.entries {
+            let location = format!("{}:{}", entry.file.display(), entry.line);
+            if let Some(first) = seen.insert(stems(&entry.term), entry) {
+                problems.push(format!(
+                    "{}: \"{}\" is already defined at {}:{}",
+                    location,
+                    entry.term,
+                    first.file.display(),
+                    first.line
+                ));
+            }
+            if entry.definition.is_empty() {
+                problems.push(format!(
+                    "{}: the comment block below `{} {}` never mentions it",
+                    location, TAG, entry.term
+                ));
+            }
+
+            for mention in self.mentions(entry)? {
+                if mention.lesson < entry.lesson {
+                    problems.push(format!(
+                        "{}:{}: uses \"{}\" before lesson {} defines it ({})",
+                        mention.file.display(),
+                        mention.line,
+                        entry.term,
+                        self.lesson(entry).id(),
+                        location
+                    ));
+                }
+            }
+        }
+
+        Ok(problems)
+    }
+}
+
+/// The terms a passage of prose introduces, with their abbreviations. A term is what something
+/// is "called" or "known as", what "we call" something in quotes, or a name in Title Case after
+/// "the", like "the Struct Update Syntax".
+fn introduced(text: &str) -> Vec<(String, Option<String>)> {
+    let mut terms = Vec::new();
+    for sentence in sentences(text) {
+        // ASCII lowercase keeps every byte where it was, so positions carry over.
+        let lower = format!(" {}", sentence.to_ascii_lowercase());
+        let padded = format!(" {}", sentence);
+
+        for marker in [" called ", " known as "] {
+            for (start, _) in lower.match_indices(marker) {
+                let before = padded[..start]
+                    .split_whitespace()
+                    .last()
+                    .unwrap_or_default();
+                let rest = &padded[start + marker.len()..];
+                let after = rest.split_whitespace().next().unwrap_or_default();
+                if marker == " called "
+                    && (CODE.contains(&before.to_ascii_lowercase().as_str())
+                        || CALLING.contains(&after.to_ascii_lowercase().as_str()))
+                {
+                    continue;
+                }
+                terms.extend(named(rest));
+            }
+        }
+
+        if let Some(start) = lower.find(" we call ") {
+            let quoted: Vec<&str> = padded[start..].split('"').collect();
+            if quoted.len() >= 3 {
+                terms.extend(clean(quoted[quoted.len() - 2]).map(|term| (term, None)));
+            }
+        }
+
+        let words: Vec<&str> = sentence.split_whitespace().collect();
+        for (index, _) in words.iter().enumerate().filter(|(_, word)| **word == "the") {
+            let mut name = Vec::new();
+            for word in &words[index + 1..] {
+                let bare = word.trim_end_matches(|c: char| !c.is_alphanumeric());
+                if !bare.starts_with(|c: char| c.is_uppercase()) {
+                    break;
+                }
+                name.push(bare);
+                if bare.len() < word.len() {
+                    break;
+                }
+            }
+            if name.len() >= 2 {
+                terms.extend(clean(&name.join(" ")).map(|term| (term, None)));
+            }
+        }
+    }
+    terms
+}
+
+/// The term at the start of `text`, which follows "called" or "known as", with an abbreviation
+/// after it as in "last in, first out, or LIFO" or "Non-Lexical Lifetimes (NLL for short)".
+fn named(text: &str) -> Option<(String, Option<String>)> {
+    // A list item ends at the next one's dash.
+    let end = [text.find(['.', '!', '?', ';', ':', '(']), text.find(" - ")]
+        .into_iter()
+        .flatten()
+        .min()
+        .unwrap_or(text.len());
+    let mut phrase = &text[..end];
+    let mut abbreviation = None;
+    if let Some((term, short)) = phrase
+        .rsplit_once(", or ")
+        .filter(|(_, short)| is_abbreviation(short.trim()))
+    {
+        phrase = term;
+        abbreviation = Some(short.trim().to_string());
+    } else if let Some(inside) = text[end..].strip_prefix('(') {
+        let short = inside.split(')').next().unwrap_or_default();
+        let short = short.trim().trim_end_matches("for short").trim();
+        if is_abbreviation(short) {
+            abbreviation = Some(short.to_string());
+        }
+        phrase = phrase.split(',').next().unwrap_or_default();
+    } else {
+        phrase = phrase.split(',').next().unwrap_or_default();
+    }
+    Some((clean(phrase)?, abbreviation))
+}
+
+/// `LIFO` or `NLL`.
+fn is_abbreviation(text: &str) -> bool {
+    text.len() >= 2 && text.chars().all(|c| c.is_ascii_uppercase())
+}
+
+/// The term in `phrase` without quotes or a leading "a" or "the", starting with a capital, or
+/// `None` when it looks like code or a whole clause rather than a name.
+fn clean(phrase: &str) -> Option<String> {
+    let mut words: Vec<&str> = phrase
+        .trim()
+        .trim_matches(|c| c == '"' || c == '\'')
+        .split_whitespace()
+        .collect();
+    while words
+        .first()
+        .is_some_and(|word| DETERMINERS.contains(&word.to_ascii_lowercase().as_str()))
+    {
+        words.remove(0);
+    }
+    let term = words.join(" ");
+    let plain = term
+        .chars()
+        .all(|c| c.is_alphabetic() || matches!(c, ' ' | ',' | '-' | '\''));
+    if words.is_empty() || words.len() > MAX_WORDS || !plain {
+        return None;
+    }
+
+    let mut chars = term.chars();
+    let first = chars.next()?;
+    Some(first.to_uppercase().chain(chars).collect())
+}
+
+/// The term and abbreviation of a tag, e.g. `Non-Lexical Lifetimes (NLL)`, or `None` when it
+/// has no words to look for.
+fn parse_term(tagged: &str) -> Option<(String, Option<String>)> {
+    let (term, abbreviation) = match tagged.trim().split_once(" (") {
+        Some((term, abbreviation)) if abbreviation.ends_with(')') => (
+            term.trim().to_string(),
+            Some(abbreviation.trim_end_matches(')').trim().to_string())
+                .filter(|abbreviation| !stems(abbreviation).is_empty()),
+        ),
+        _ => (tagged.trim().to_string(), None),
+    };
+    if stems(&term).is_empty() {
+        return None;
+    }
+    Some((term, abbreviation))
+}
+
+/// The lesson's sources and challenges.
+fn sources(lesson: &Lesson) -> Result<Vec<PathBuf>> {
+    let mut files = expect::sources(&lesson.dir.join("src"))?;
+    files.extend(
+        lesson
+            .challenges
+            .iter()
+            .map(|challenge| lesson.challenge_path(challenge)),
+    );
+    Ok(files)
+}
+
+fn relative(lesson: &Lesson, file: &Path) -> PathBuf {
+    let within = file.strip_prefix(&lesson.dir).unwrap_or(file);
+    Path::new("lessons").join(&lesson.slug).join(within)
+}
+
+/// The line that first mentions `term` in the comment block after `tag_line`, and its definition.
+fn define(
+    term: &str,
+    passages: &[(Kind, usize, Vec<String>)],
+    tag_line: usize,
+) -> Option<(usize, String)> {
+    let term = stems(term);
+    if term.is_empty() {
+        return None;
+    }
+    let block = passages
+        .iter()
+        .skip_while(|(_, first, _)| *first <= tag_line)
+        .take_while(|(kind, _, _)| *kind == Kind::Prose);
+
+    for (_, first, lines) in block {
+        let Some(offset) = lines
+            .iter()
+            .position(|line| contains(&stems(line), &term[..1]))
+        else {
+            continue;
+        };
+        let sentences = sentences(&lines.join(" "));
+        let Some(defining) = sentences
+            .iter()
+            .position(|sentence| contains(&stems(sentence), &term))
+        else {
+            continue;
+        };
+
+        let refers_back = sentences[defining]
+            .split_whitespace()
+            .next()
+            .is_some_and(|word| REFERRING.contains(&word.to_lowercase().as_str()));
+        let start = if refers_back {
+            defining.saturating_sub(1)
+        } else {
+            defining
+        };
+        let end = (defining + 2).min(sentences.len());
+
+        return Some((first + offset, sentences[start..end].join(" ")));
+    }
+
+    None
+}
+
+/// Splits a paragraph after every `.`, `!` or `?` that's followed by a new sentence.
+fn sentences(text: &str) -> Vec<String> {
+    let mut sentences = Vec::new();
+    let mut current = String::new();
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        current.push(c);
+        if matches!(c, '.' | '!' | '?') && chars.peek() == Some(&' ') {
+            let rest: String = chars.clone().skip(1).take(1).collect();
+            if rest.starts_with(|c: char| c.is_uppercase() || c == '"') {
+                sentences.push(current.trim().to_string());
+                current.clear();
+            }
+        }
+    }
+    if !current.trim().is_empty() {
+        sentences.push(current.trim().to_string());
+    }
+
+    sentences
+}
+
+/// The words of `text`, with a plural "s" dropped so "reference" and "references" match.
+fn stems(text: &str) -> Vec<String> {
+    search::words(text)
+        .into_iter()
+        .map(|word| match word.strip_suffix('s') {
+            Some(stem) if stem.len() > 2 && !stem.ends_with('s') => stem.to_string(),
+            _ => word,
+        })
+        .collect()
+}
+
+/// Whether `needle` appears in `words` as consecutive words.
+fn contains(words: &[String], needle: &[String]) -> bool {
+    !needle.is_empty() && words.windows(needle.len()).any(|window| window == needle)
+}
+
+/// Prints every term, alphabetically, with where it's defined.
+pub fn print_list(glossary: &Glossary) {
+    let mut entries: Vec<&Entry> = glossary.entries.iter().collect();
+    entries.sort_by_key(|entry| entry.term.to_lowercase());
+    let width = entries
+        .iter()
+        .map(|entry| entry.title().chars().count())
+        .max()
+        .unwrap_or(0);
+
+    for entry in entries {
+        println!(
+            "{:width$}  {}  {}:{}",
+            entry.title(),
+            glossary.lesson(entry).id(),
+            entry.file.display(),
+            entry.line
+        );
+    }
+}
+
+pub fn print(glossary: &Glossary, entry: &Entry, mentions: &[Mention]) {
+    let lesson = glossary.lesson(entry);
+    println!("{}", entry.title());
+    println!(
+        "  defined in lesson {} {} ({}:{})\n",
+        lesson.id(),
+        lesson.metadata.title,
+        entry.file.display(),
+        entry.line
+    );
+    for line in wrap(&entry.definition) {
+        println!("  > {}", line);
+    }
+
+    let mut later: Vec<String> = mentions
+        .iter()
+        .filter(|mention| mention.lesson > entry.lesson)
+        .map(|mention| glossary.lessons[mention.lesson].id())
+        .collect();
+    later.dedup();
+    if !later.is_empty() {
+        println!("\n  also used in lesson {}", later.join(", "));
+    }
+}
+
+fn wrap(text: &str) -> Vec<String> {
+    let mut lines = vec![String::new()];
+    for word in text.split_whitespace() {
+        let line = lines.last_mut().unwrap();
+        if !line.is_empty() && line.len() + 1 + word.len() > WIDTH {
+            lines.push(word.to_string());
+        } else {
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(word);
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tags_name_a_term_and_maybe_an_abbreviation() {
+        assert_eq!(
+            parse_term(" Shadowing"),
+            Some(("Shadowing".to_string(), None))
+        );
+        assert_eq!(
+            parse_term(" Non-Lexical Lifetimes (NLL)"),
+            Some(("Non-Lexical Lifetimes".to_string(), Some("NLL".to_string())))
+        );
+        assert_eq!(
+            parse_term(" Unit-Like Structs ( )"),
+            Some(("Unit-Like Structs".to_string(), None))
+        );
+    }
+
+    fn terms(text: &str) -> Vec<(String, Option<String>)> {
+        introduced(text)
+    }
+
+    fn term(name: &str) -> (String, Option<String>) {
+        (name.to_string(), None)
+    }
+
+    #[test]
+    fn what_something_is_called() {
+        assert_eq!(
+            terms("There's also a way to redefine immutable variables in Rust. It's called Shadowing."),
+            [term("Shadowing")]
+        );
+        assert_eq!(
+            terms(
+                "Those are called unit-like structs, because they behave similarly to the () type."
+            ),
+            [term("Unit-like structs")]
+        );
+        assert_eq!(
+            terms(
+                "Adding data to the stack is called pushing, and removing data is called popping."
+            ),
+            [term("Pushing"), term("Popping")]
+        );
+        assert_eq!(
+            terms("The 32-bit float is also known as a single precision float."),
+            [term("Single precision float")]
+        );
+    }
+
+    #[test]
+    fn what_we_call_something_in_quotes() {
+        assert_eq!(
+            terms("We call the action of creating references \"borrowing\"."),
+            [term("Borrowing")]
+        );
+    }
+
+    #[test]
+    fn names_in_title_case_after_the() {
+        assert_eq!(
+            terms("We can also use the Field Init Shorthand when the variables and field names match."),
+            [term("Field Init Shorthand")]
+        );
+        assert_eq!(terms("We can also use the Ownership system."), []);
+    }
+
+    #[test]
+    fn abbreviations_follow_the_term() {
+        assert_eq!(
+            terms("That is called Non-Lexical Lifetimes (NLL for short)."),
+            [("Non-Lexical Lifetimes".to_string(), Some("NLL".to_string()))]
+        );
+        assert_eq!(
+            terms("This way of functioning is called last in, first out, or LIFO."),
+            [("Last in, first out".to_string(), Some("LIFO".to_string()))]
+        );
+        assert_eq!(
+            terms("This process is called allocating on the heap, or just allocating."),
+            [term("Allocating on the heap")]
+        );
+    }
+
+    #[test]
+    fn code_and_clauses_are_not_terms() {
+        assert_eq!(
+            terms("We've created a new function called inner_function after the main function."),
+            []
+        );
+        assert_eq!(
+            terms(
+                "Rust calls a special function called \"drop\", which is the code that frees it."
+            ),
+            []
+        );
+        assert_eq!(
+            terms("The function is called with every number up to the one we passed in."),
+            []
+        );
+        assert_eq!(terms("It's called once per item."), []);
+    }
+
+    #[test]
+    fn a_list_item_ends_at_the_next_one() {
+        assert_eq!(
+            terms(
+                "Each value has a variable that's called its owner - There can only be one owner."
+            ),
+            [term("Owner")]
+        );
+    }
+
+    #[test]
+    fn tags_without_a_term_are_rejected() {
+        assert_eq!(parse_term(""), None);
+        assert_eq!(parse_term("   "), None);
+        assert_eq!(parse_term(" ..."), None);
+    }
+}
@@ -0,0 +1,284 @@
+//! Compiles a learner's challenge together with hidden test modules and runs the tests.
+//!
+//! The challenge is pulled in with `include!`, so the tests can call the learner's functions
+//! directly. Building with `rustc --test` means the learner's own `main` is simply never called.
+
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::diagnostics::{self, Diagnostic};
+use crate::error::{Error, Result};
+use crate::exec::{self, Limits};
+use crate::lesson::Lesson;
+
+/// A hidden module to compile next to the learner's code, as `mod <name> { include!(<path>) }`.
+#[derive(Clone)]
+pub struct Module<'a> {
+    pub name: &'a str,
+    pub path: PathBuf,
+}
+
+pub enum TestRun {
+    /// The learner's code didn't compile, or doesn't have the functions the tests call.
+    BuildFailed(Vec<Diagnostic>),
+    Ran(Vec<TestResult>),
+}
+
+pub struct TestResult {
+    /// The test function's name, without its module path.
+    pub name: String,
+    pub passed: bool,
+    /// The panic message of a failed test, e.g. the assertion that didn't hold.
+    pub message: String,
+}
+
+impl TestResult {
+    /// A test that started but never reported back, because the run was stopped.
+    fn unfinished(&self) -> bool {
+        !self.passed && self.message.is_empty()
+    }
+}
+
+pub fn run(
+    lesson: &Lesson,
+    challenge: &str,
+    modules: &[Module],
+    limits: &Limits,
+) -> Result<TestRun> {
+    let scratch = lesson.scratch_dir(challenge);
+    fs::create_dir_all(&scratch).map_err(|e| Error::io(&scratch, e))?;
+
+    let source = scratch.join("harness.rs");
+    let contents = harness_source(&lesson.challenge_path(challenge), modules);
+    fs::write(&source, contents).map_err(|e| Error::io(&source, e))?;
+
+    let binary = scratch.join("harness");
+    let rustc = std::env::var_os("RUSTC").unwrap_or_else(|| "rustc".into());
+    let mut command = Command::new(rustc);
+    command
+        .arg("--test")
+        .arg("--edition")
+        .arg(&lesson.edition)
+        .arg("--error-format=json")
+        // The learner's code isn't ours to lint, and its unused `main` would warn anyway.
+        .arg("--cap-lints=allow")
+        .arg("-o")
+        .arg(&binary)
+        .arg(&source);
+
+    let output = command.output().map_err(|source| Error::Spawn {
+        program: command.get_program().to_string_lossy().into_owned(),
+        source,
+    })?;
+    if !output.status.success() {
+        let errors = diagnostics::parse_rustc(&String::from_utf8_lossy(&output.stderr))
+            .into_iter()
+            // Drop the spanless "aborting due to N previous errors" summary.
+            .filter(|diagnostic| diagnostic.is_error() && !diagnostic.spans.is_empty())
+            .collect();
+        return Ok(TestRun::BuildFailed(errors));
+    }
+
+    let mut command = Command::new(&binary);
+    command
+        .arg("--test-threads=1")
+        .env("RUST_BACKTRACE", "0")
+        .current_dir(&lesson.dir);
+    let run = exec::execute(command, limits, false)?;
+
+    let mut results = parse_results(&run.stdout);
+    if let Some(exceeded) = run.exceeded {
+        // Tests run one at a time, so the one that never reported back is the culprit.
+        match results.iter_mut().find(|result| result.unfinished()) {
+            Some(result) => result.message = format!("it {}", exceeded),
+            None => results.push(TestResult {
+                name: "(all tests)".to_string(),
+                passed: false,
+                message: format!("they {}", exceeded),
+            }),
+        }
+    }
+
+    Ok(TestRun::Ran(results))
+}
+
+fn harness_source(challenge: &Path, modules: &[Module]) -> String {
+    let mut source = String::new();
+    writeln!(source, "include!({:?});", challenge.display().to_string()).unwrap();
+    for module in modules {
+        writeln!(
+            source,
+            "\n#[cfg(test)]\nmod {} {{\n    #[allow(unused_imports)]\n    use super::*;\n\n    include!({:?});\n}}",
+            module.name,
+            module.path.display().to_string()
+        )
+        .unwrap();
+    }
+    source
+}
+
+/// Reads libtest's human-readable output: the `test <name> ... ok` lines,
+/// followed by a `---- <name> stdout ----` section for every failure, of which only the panic
+/// message is kept.
+fn parse_results(stdout: &str) -> Vec<TestResult> {
+    let mut results: Vec<TestResult> = Vec::new();
+    let mut failure: Option<usize> = None;
+
+    for line in stdout.lines() {
+        if let Some(rest) = line.strip_prefix("test ") {
+            if let Some((name, status)) = rest.rsplit_once(" ... ") {
+                results.push(TestResult {
+                    name: short_name(name),
+                    passed: status == "ok",
+                    message: String::new(),
+                });
+                continue;
+            }
+        }
+
+        if let Some(name) = line
+            .strip_prefix("---- ")
+            .and_then(|rest| rest.strip_suffix(" stdout ----"))
+        {
+            let name = short_name(name);
+            failure = results.iter().position(|result| result.name == name);
+            continue;
+        }
+        if line == "failures:" || line.starts_with("test result:") {
+            failure = None;
+            continue;
+        }
+
+        if let Some(index) = failure {
+            // What the test printed comes before the panic, and only the panic message is kept.
+            if line.starts_with("thread '") {
+                results[index].message.clear();
+                continue;
+            }
+            // Skip the noise around the actual panic message.
+            if line.starts_with("note: ") || line.is_empty() {
+                continue;
+            }
+            let message = &mut results[index].message;
+            if !message.is_empty() {
+                message.push('\n');
+            }
+            message.push_str(line);
+        }
+    }
+
+    results
+}
+
+/// `hidden_tests::points_70_is_an_8` -> `points_70_is_an_8`
+fn short_name(name: &str) -> String {
+    name.rsplit("::").next().unwrap_or(name).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn passed_and_failed_tests() {
+        let results = parse_results(
+            "
+running 3 tests
+test hidden_tests::points_96_is_a_10 ... ok
+test hidden_tests::points_70_is_an_8 ... FAILED
+test properties::grades_match_the_reference ... ok
+
+failures:
+
+---- hidden_tests::points_70_is_an_8 stdout ----
+
+thread 'hidden_tests::points_70_is_an_8' (8114) panicked at src/main.rs:3:59:
+assertion `left == right` failed: 70 points is an 8
+  left: 7
+ right: 8
+note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace
+
+
+failures:
+    hidden_tests::points_70_is_an_8
+
+test result: FAILED. 2 passed; 1 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s
+",
+        );
+
+        let summary: Vec<(&str, bool)> = results
+            .iter()
+            .map(|result| (result.name.as_str(), result.passed))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("points_96_is_a_10", true),
+                ("points_70_is_an_8", false),
+                ("grades_match_the_reference", true),
+            ]
+        );
+        assert_eq!(results[0].message, "");
+        assert_eq!(
+            results[1].message,
+            "assertion `left == right` failed: 70 points is an 8\n  left: 7\n right: 8"
+        );
+    }
+
+    #[test]
+    fn what_a_failed_test_printed_is_left_out() {
+        let results = parse_results(
+            "
+running 2 tests
+test hidden_tests::a ... FAILED
+test hidden_tests::b ... FAILED
+
+failures:
+
+---- hidden_tests::a stdout ----
+You've scored 70 points
+
+thread 'hidden_tests::a' (8114) panicked at src/main.rs:3:59:
+the grade is wrong
+
+---- hidden_tests::b stdout ----
+
+thread 'hidden_tests::b' (8115) panicked at src/main.rs:4:22:
+boom
+
+
+failures:
+    hidden_tests::a
+    hidden_tests::b
+
+test result: FAILED. 0 passed; 2 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s
+",
+        );
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].message, "the grade is wrong");
+        assert_eq!(results[1].message, "boom");
+    }
+
+    #[test]
+    fn a_run_stopped_in_the_middle_of_a_test() {
+        let results = parse_results(
+            "
+running 3 tests
+test hidden_tests::a ... ok
+test hidden_tests::b ... ",
+        );
+
+        assert_eq!(results.len(), 2);
+        assert!(results[0].passed && !results[0].unfinished());
+        assert!(results[1].unfinished());
+        assert_eq!(results[1].name, "b");
+    }
+
+    #[test]
+    fn no_output_at_all() {
+        assert!(parse_results("").is_empty());
+    }
+}
@@ -0,0 +1,114 @@
+//! A small Rust syntax highlighter that emits HTML, good enough for the lessons' snippets.
+//!
+//! It only tells apart comments, strings, characters, numbers, keywords, macros, attributes and
+//! type-like names. Everything else is escaped and passed through as-is.
+
+const KEYWORDS: &[&str] = &[
+    "as", "break", "const", "continue", "crate", "else", "enum", "false", "fn", "for", "if",
+    "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref", "return", "self",
+    "Self", "static", "struct", "super", "trait", "true", "type", "unsafe", "use", "where",
+    "while",
+];
+
+/// Escapes the characters that mean something in HTML.
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Highlights one line of Rust code, wrapping each token in a `<span class="...">`.
+pub fn line(code: &str) -> String {
+    let chars: Vec<char> = code.chars().collect();
+    let mut html = String::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let start = i;
+
+        let class = if c == '/' && chars.get(i + 1) == Some(&'/') {
+            i = chars.len();
+            "comment"
+        } else if c == '"' {
+            i = closing_quote(&chars, i + 1, '"');
+            "string"
+        } else if c == '\'' && chars.get(i + 2) == Some(&'\'') {
+            i += 3;
+            "string"
+        } else if c == '\'' && chars.get(i + 1) == Some(&'\\') {
+            i = closing_quote(&chars, i + 1, '\'');
+            "string"
+        } else if c == '#' && chars.get(i + 1) == Some(&'[') {
+            i = chars[i..]
+                .iter()
+                .position(|&c| c == ']')
+                .map_or(chars.len(), |end| i + end + 1);
+            "attribute"
+        } else if c.is_ascii_digit() {
+            while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            // A fraction, but not a range like `1..3`.
+            if chars.get(i) == Some(&'.') && chars.get(i + 1).is_some_and(char::is_ascii_digit) {
+                i += 1;
+                while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+            }
+            "number"
+        } else if c.is_alphabetic() || c == '_' {
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            let word: String = chars[start..i].iter().collect();
+            if chars.get(i) == Some(&'!') && chars.get(i + 1) != Some(&'=') {
+                i += 1;
+                "macro"
+            } else if KEYWORDS.contains(&word.as_str()) {
+                "keyword"
+            } else if word.starts_with(char::is_uppercase) {
+                "type"
+            } else {
+                ""
+            }
+        } else {
+            i += 1;
+            ""
+        };
+
+        let text: String = chars[start..i].iter().collect();
+        if class.is_empty() {
+            html.push_str(&escape(&text));
+        } else {
+            html.push_str(&format!(
+                "<span class=\"{}\">{}</span>",
+                class,
+                escape(&text)
+            ));
+        }
+    }
+
+    html
+}
+
+/// The index right after the quote that closes a literal starting at `from`, skipping escapes.
+fn closing_quote(chars: &[char], from: usize, quote: char) -> usize {
+    let mut i = from;
+    while i < chars.len() {
+        match chars[i] {
+            '\\' => i += 2,
+            c if c == quote => return i + 1,
+            _ => i += 1,
+        }
+    }
+    chars.len()
+}
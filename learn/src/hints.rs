@@ -0,0 +1,128 @@
+//! Progressively revealed hints for a challenge.
+//!
+//! A challenge's hints live in `checks/<challenge>/hints.md`, separated by lines that only
+//! contain `---`. They go from a gentle nudge to almost the full solution, so every
+//! `learn hint` reveals just one more.
+
+use std::fs;
+
+use crate::error::{Error, Result};
+use crate::lesson::Lesson;
+
+pub const HINTS: &str = "hints.md";
+
+pub fn load(lesson: &Lesson, challenge: &str) -> Result<Vec<String>> {
+    let path = lesson.checks_dir(challenge).join(HINTS);
+    if !path.is_file() {
+        return Err(Error::NoHints {
+            lesson: lesson.id(),
+            challenge: challenge.to_string(),
+        });
+    }
+    let contents = fs::read_to_string(&path).map_err(|e| Error::io(&path, e))?;
+
+    // A file with nothing but separators in it has no hints either.
+    let hints = split(&contents);
+    if hints.is_empty() {
+        return Err(Error::NoHints {
+            lesson: lesson.id(),
+            challenge: challenge.to_string(),
+        });
+    }
+    Ok(hints)
+}
+
+fn split(contents: &str) -> Vec<String> {
+    let mut hints = vec![String::new()];
+    for line in contents.lines() {
+        if line.trim() == "---" {
+            hints.push(String::new());
+        } else if let Some(hint) = hints.last_mut() {
+            hint.push_str(line);
+            hint.push('\n');
+        }
+    }
+
+    hints
+        .into_iter()
+        .map(|hint| hint.trim().to_string())
+        .filter(|hint| !hint.is_empty())
+        .collect()
+}
+
+/// Prints the hints up to and including `revealed`, or just the latest one unless `all` is set.
+pub fn print(lesson: &Lesson, challenge: &str, hints: &[String], revealed: usize, all: bool) {
+    let first = if all { 0 } else { revealed.saturating_sub(1) };
+
+    for (index, hint) in hints.iter().enumerate().take(revealed).skip(first) {
+        println!(
+            "Hint {} of {} for {} {}:\n",
+            index + 1,
+            hints.len(),
+            lesson.id(),
+            challenge
+        );
+        println!("{}\n", hint);
+    }
+
+    if revealed < hints.len() {
+        println!("Run the same command again for the next hint.");
+    } else {
+        println!("That's the last hint. You've got this!");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lesson;
+    use std::path::Path;
+
+    /// Loads the grader's hints from a `hints.md` with these contents, in a copy of lesson 04.
+    fn load_from(name: &str, contents: &str) -> Result<Vec<String>> {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+        let lessons = lesson::discover(root).unwrap();
+        let mut lesson = lesson::find(&lessons, "04").unwrap().clone();
+        lesson.dir =
+            std::env::temp_dir().join(format!("learn-hints-{}-{}", std::process::id(), name));
+        let dir = lesson.checks_dir("grader");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join(HINTS), contents).unwrap();
+
+        let hints = load(&lesson, "grader");
+        let _ = fs::remove_dir_all(&lesson.dir);
+        hints
+    }
+
+    #[test]
+    fn an_empty_file_has_no_hints() {
+        assert!(matches!(load_from("empty", ""), Err(Error::NoHints { .. })));
+        assert!(matches!(
+            load_from("separators", "---\n\n---\n"),
+            Err(Error::NoHints { .. })
+        ));
+    }
+
+    #[test]
+    fn a_file_without_separators_is_one_hint() {
+        let hints = load_from(
+            "single",
+            "\nUse `%` for the remainder.\nIt's zero when divisible.\n\n",
+        )
+        .unwrap();
+        assert_eq!(
+            hints,
+            ["Use `%` for the remainder.\nIt's zero when divisible."]
+        );
+    }
+
+    #[test]
+    fn separators_split_the_stages_and_empty_ones_are_skipped() {
+        let hints = load_from(
+            "stages",
+            "A nudge.\n---\n---\n  ---  \nMore.\n\n---\nThe answer.\n",
+        )
+        .unwrap();
+        assert_eq!(hints, ["A nudge.", "More.", "The answer."]);
+    }
+}
@@ -0,0 +1,222 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::error::{Error, Result};
+use crate::metadata::{self, Metadata};
+
+/// A single `lessons/NN-name` cargo project.
+#[derive(Debug, Clone)]
+pub struct Lesson {
+    /// The `NN` prefix of the directory, e.g. `4` for `04-control-flow`.
+    pub number: u32,
+    /// The directory name, e.g. `04-control-flow`.
+    pub slug: String,
+    /// The package name from the lesson's `Cargo.toml`, e.g. `control-flow`.
+    pub name: String,
+    pub dir: PathBuf,
+    /// The Rust edition the lesson is written in, e.g. `2021`.
+    pub edition: String,
+    /// The challenges in the lesson's `examples` directory, in the order its metadata lists them.
+    pub challenges: Vec<String>,
+    /// Lessons that intentionally don't compile carry a `fixed` feature with the corrected code.
+    pub has_fixed_variant: bool,
+    pub metadata: Metadata,
+}
+
+/// The cargo feature that swaps a broken lesson's failing lines for their fixes.
+pub const FIXED_FEATURE: &str = "fixed";
+
+#[derive(Deserialize)]
+struct Manifest {
+    package: Package,
+    #[serde(default)]
+    features: BTreeMap<String, Vec<String>>,
+}
+
+#[derive(Deserialize)]
+struct Package {
+    name: String,
+    #[serde(default = "default_edition")]
+    edition: String,
+    #[serde(default)]
+    metadata: PackageMetadata,
+}
+
+#[derive(Default, Deserialize)]
+struct PackageMetadata {
+    lesson: Option<Metadata>,
+}
+
+/// What cargo assumes when a manifest doesn't say.
+fn default_edition() -> String {
+    "2015".to_string()
+}
+
+impl Lesson {
+    fn load(dir: &Path, number: u32, slug: String) -> Result<Self> {
+        let manifest_path = dir.join("Cargo.toml");
+        let contents =
+            fs::read_to_string(&manifest_path).map_err(|e| Error::io(&manifest_path, e))?;
+        let manifest: Manifest = toml::from_str(&contents).map_err(|source| Error::Manifest {
+            path: manifest_path.clone(),
+            source,
+        })?;
+
+        let Some(lesson_metadata) = manifest.package.metadata.lesson else {
+            let problem = "missing the `[package.metadata.lesson]` table".to_string();
+            return Err(Error::Metadata {
+                path: manifest_path,
+                problems: vec![problem],
+            });
+        };
+        let examples = challenges(&dir.join("examples"))?;
+        metadata::check(
+            &manifest_path,
+            metadata::problems(&lesson_metadata, &examples),
+        )?;
+
+        Ok(Lesson {
+            number,
+            slug,
+            name: manifest.package.name,
+            dir: dir.to_path_buf(),
+            edition: manifest.package.edition,
+            challenges: lesson_metadata
+                .challenges
+                .iter()
+                .map(|challenge| challenge.name.clone())
+                .collect(),
+            has_fixed_variant: manifest.features.contains_key(FIXED_FEATURE),
+            metadata: lesson_metadata,
+        })
+    }
+
+    /// The two-digit id used on the command line, e.g. `04`.
+    pub fn id(&self) -> String {
+        format!("{:02}", self.number)
+    }
+
+    pub fn manifest_path(&self) -> PathBuf {
+        self.dir.join("Cargo.toml")
+    }
+
+    /// The learner's source file for a challenge, e.g. `examples/grader.rs`.
+    pub fn challenge_path(&self, challenge: &str) -> PathBuf {
+        self.dir.join("examples").join(format!("{}.rs", challenge))
+    }
+
+    /// Where a challenge's specs and hidden tests live, e.g. `checks/grader`.
+    pub fn checks_dir(&self, challenge: &str) -> PathBuf {
+        self.dir.join("checks").join(challenge)
+    }
+
+    /// A throwaway directory for the runner's own build products, inside the lesson's `target`.
+    pub fn scratch_dir(&self, name: &str) -> PathBuf {
+        self.dir.join("target").join("learn").join(name)
+    }
+
+    /// What the challenge asks for, as described in the lesson's metadata.
+    pub fn description(&self, challenge: &str) -> &str {
+        self.metadata
+            .challenges
+            .iter()
+            .find(|metadata| metadata.name == challenge)
+            .map_or("", |metadata| metadata.description.as_str())
+    }
+
+    pub fn challenge(&self, name: &str) -> Result<&str> {
+        self.challenges
+            .iter()
+            .find(|challenge| *challenge == name)
+            .map(String::as_str)
+            .ok_or_else(|| Error::ChallengeNotFound {
+                lesson: self.id(),
+                name: name.to_string(),
+            })
+    }
+
+    /// Whether `query` refers to this lesson. Accepts `04`, `4`, `control-flow` and `04-control-flow`.
+    fn matches(&self, query: &str) -> bool {
+        query.parse::<u32>().ok() == Some(self.number) || query == self.name || query == self.slug
+    }
+}
+
+fn challenges(examples: &Path) -> Result<Vec<String>> {
+    if !examples.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut names = Vec::new();
+    for entry in fs::read_dir(examples).map_err(|e| Error::io(examples, e))? {
+        let path = entry.map_err(|e| Error::io(examples, e))?.path();
+        if path.extension().is_some_and(|ext| ext == "rs") {
+            if let Some(stem) = path.file_stem().and_then(|stem| stem.to_str()) {
+                names.push(stem.to_string());
+            }
+        }
+    }
+    names.sort();
+
+    Ok(names)
+}
+
+/// Extracts `4` from `04-control-flow`. Anything that isn't `NN-*` is not a lesson.
+fn parse_dir_name(name: &str) -> Option<u32> {
+    let (number, rest) = name.split_once('-')?;
+    if rest.is_empty() || number.is_empty() || !number.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    number.parse().ok()
+}
+
+/// Finds every lesson under `root/lessons`, in the order their metadata puts them.
+pub fn discover(root: &Path) -> Result<Vec<Lesson>> {
+    let lessons_dir = root.join("lessons");
+    let mut lessons = Vec::new();
+
+    for entry in fs::read_dir(&lessons_dir).map_err(|e| Error::io(&lessons_dir, e))? {
+        let path = entry.map_err(|e| Error::io(&lessons_dir, e))?.path();
+        let Some(slug) = path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        let Some(number) = parse_dir_name(slug) else {
+            continue;
+        };
+        if !path.join("Cargo.toml").is_file() {
+            continue;
+        }
+
+        lessons.push(Lesson::load(&path, number, slug.to_string())?);
+    }
+    lessons.sort_by_key(|lesson| lesson.metadata.order);
+    metadata::validate_course(&lessons)?;
+
+    Ok(lessons)
+}
+
+pub fn find<'a>(lessons: &'a [Lesson], query: &str) -> Result<&'a Lesson> {
+    lessons
+        .iter()
+        .find(|lesson| lesson.matches(query))
+        .ok_or_else(|| Error::LessonNotFound(query.to_string()))
+}
+
+/// Walks up from the current directory until it finds the one containing `lessons/`.
+/// Falls back to the repo this binary was built from.
+pub fn find_root() -> Result<PathBuf> {
+    if let Ok(cwd) = std::env::current_dir() {
+        if let Some(root) = cwd.ancestors().find(|dir| dir.join("lessons").is_dir()) {
+            return Ok(root.to_path_buf());
+        }
+    }
+
+    let built_from = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+    if built_from.join("lessons").is_dir() {
+        return Ok(built_from);
+    }
+
+    Err(Error::RootNotFound)
+}
@@ -0,0 +1,432 @@
+//! `learn` drives the lessons in this repo from a single place.
+//!
+//! Every `lessons/NN-*` directory is discovered automatically, so adding a new lesson
+//! doesn't require touching the runner.
+
+mod book;
+mod browse;
+mod cargo;
+mod check;
+mod comments;
+mod copy;
+mod diagnostics;
+mod diff;
+mod error;
+mod exec;
+mod expect;
+mod explain;
+mod glossary;
+mod harness;
+mod highlight;
+mod hints;
+mod lesson;
+mod metadata;
+mod progress;
+mod search;
+mod serve;
+mod snapshot;
+mod source;
+mod timestamp;
+mod tour;
+mod try_error;
+mod watch;
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use clap::{Parser, Subcommand};
+
+use crate::error::{Error, Result};
+use crate::lesson::Lesson;
+use crate::progress::Progress;
+
+#[derive(Parser)]
+#[command(name = "learn", about = "Run the Learning Rust lessons and challenges")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+    #[command(flatten)]
+    limits: exec::Limits,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// List every lesson and its challenges, in course order
+    List,
+    /// Run a lesson, or one of its challenges
+    Run {
+        /// Lesson number or name, e.g. `04` or `control-flow`
+        lesson: String,
+        /// Run a challenge from the lesson's `examples` directory instead
+        #[arg(long)]
+        example: Option<String>,
+        /// Apply the fixes of a lesson that intentionally doesn't compile
+        #[arg(long)]
+        fixed: bool,
+    },
+    /// Check a challenge solution against its expected output, hidden tests and properties
+    Check {
+        /// Lesson number or name, e.g. `04` or `control-flow`
+        lesson: String,
+        /// The challenge to check; checks all of the lesson's challenges when omitted
+        challenge: Option<String>,
+    },
+    /// Re-run a lesson or challenge every time one of its files is saved
+    Watch {
+        /// Lesson number or name, e.g. `04` or `control-flow`
+        lesson: String,
+        /// Watch this challenge instead of the lesson's `main`
+        challenge: Option<String>,
+        /// Re-check the challenge instead of just running it
+        #[arg(long, requires = "challenge")]
+        check: bool,
+    },
+    /// Reveal the next hint for a challenge
+    Hint {
+        /// Lesson number or name, e.g. `04` or `control-flow`
+        lesson: String,
+        challenge: String,
+        /// Also show the hints revealed earlier
+        #[arg(long)]
+        all: bool,
+    },
+    /// Show which lessons you've viewed and which challenges you've passed
+    Status,
+    /// Check that lessons fail to compile exactly where their `~ERROR` markers say
+    Expect {
+        /// Only check this lesson; checks every lesson when omitted
+        lesson: Option<String>,
+    },
+    /// Uncomment one of a lesson's `@try-error` snippets in a scratch copy and show rustc's error
+    Try {
+        /// Lesson number or name, e.g. `01` or `variables`
+        lesson: String,
+        /// The snippet to try, e.g. `immutable-assign`; lists the lesson's snippets when omitted
+        snippet: Option<String>,
+    },
+    /// Generate a browsable HTML book from the lessons' comments and code
+    Book {
+        /// Where to write the book; defaults to `target/book` in the repo
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+    /// Search the lessons' explanations and code, best matches first
+    Search {
+        /// The words to look for, e.g. `struct update syntax`
+        #[arg(required = true)]
+        terms: Vec<String>,
+        /// How many matches to show
+        #[arg(long, default_value_t = 10)]
+        limit: usize,
+    },
+    /// Look up a term the lessons define, or list them all
+    Glossary {
+        /// The term to look up, e.g. `struct update syntax`
+        term: Vec<String>,
+        /// Check that no lesson uses a term before the lesson that defines it
+        #[arg(long, conflicts_with = "term")]
+        check: bool,
+    },
+    /// Walk through a lesson one comment block at a time, showing what each piece of code prints
+    Tour {
+        /// Lesson number or name, e.g. `04` or `control-flow`
+        lesson: String,
+    },
+    /// Browse the lessons in a terminal UI, and run and check them from there
+    Browse,
+    /// Serve the lessons as a web playground where challenges can be edited, run and checked
+    Serve {
+        /// The address to listen on; use `0.0.0.0:8000` to let others on your network in
+        #[arg(long, default_value = "127.0.0.1:8000")]
+        bind: String,
+    },
+    /// Check that every lesson still prints what its snapshot says
+    Snapshot {
+        /// Only check this lesson; checks every lesson when omitted
+        lesson: Option<String>,
+        /// Save the current output as the new snapshot instead of failing on changes
+        #[arg(long)]
+        accept: bool,
+    },
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    match run(cli.command, &cli.limits) {
+        Ok(code) => code,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(command: Command, limits: &exec::Limits) -> Result<ExitCode> {
+    let root = lesson::find_root()?;
+    let lessons = lesson::discover(&root)?;
+    let guide = explain::Guide::load(&lessons)?;
+
+    match command {
+        Command::List => {
+            list(&lessons);
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::Run {
+            lesson,
+            example,
+            fixed,
+        } => {
+            let lesson = lesson::find(&lessons, &lesson)?;
+            if fixed && !lesson.has_fixed_variant {
+                return Err(Error::NoFixedVariant(lesson.id()));
+            }
+            let passed = exec::build_and_run(lesson, example.as_deref(), fixed, limits, &guide)?;
+
+            // Lessons 05 and 06 never build without --fixed, and that still counts as viewing them.
+            if example.is_none() {
+                let mut progress = Progress::load(&root)?;
+                progress.mark_viewed(lesson);
+                progress.save(&root)?;
+            }
+
+            Ok(success_if(passed))
+        }
+        Command::Check { lesson, challenge } => {
+            let lesson = lesson::find(&lessons, &lesson)?;
+            let challenges = match challenge {
+                Some(challenge) => vec![lesson.challenge(&challenge)?.to_string()],
+                None => lesson.challenges.clone(),
+            };
+
+            let passed = check_challenges(&root, lesson, &challenges, limits, &guide)?;
+            Ok(success_if(passed))
+        }
+        Command::Watch {
+            lesson,
+            challenge,
+            check,
+        } => {
+            let lesson = lesson::find(&lessons, &lesson)?;
+            let challenge = challenge
+                .map(|challenge| lesson.challenge(&challenge).map(str::to_string))
+                .transpose()?;
+            let label = match &challenge {
+                Some(challenge) => format!("{} {}", lesson.id(), challenge),
+                None => format!("{} {}", lesson.id(), lesson.name),
+            };
+
+            watch::watch(lesson, &label, || match &challenge {
+                Some(challenge) if check => check_challenges(
+                    &root,
+                    lesson,
+                    std::slice::from_ref(challenge),
+                    limits,
+                    &guide,
+                ),
+                challenge => {
+                    exec::build_and_run(lesson, challenge.as_deref(), false, limits, &guide)
+                }
+            })?;
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::Hint {
+            lesson,
+            challenge,
+            all,
+        } => {
+            let lesson = lesson::find(&lessons, &lesson)?;
+            let challenge = lesson.challenge(&challenge)?;
+            let hints = hints::load(lesson, challenge)?;
+
+            let mut progress = Progress::load(&root)?;
+            let revealed = progress.reveal_hint(lesson, challenge, hints.len());
+            progress.save(&root)?;
+
+            hints::print(lesson, challenge, &hints, revealed, all);
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::Status => {
+            progress::print_status(&lessons, &Progress::load(&root)?);
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::Expect { lesson } => {
+            let selected = match lesson {
+                Some(query) => vec![lesson::find(&lessons, &query)?],
+                None => lessons.iter().collect(),
+            };
+
+            let mut passed = true;
+            for lesson in selected {
+                let report = expect::verify(lesson)?;
+                expect::print(lesson, &report);
+                passed &= report.passed();
+            }
+
+            Ok(success_if(passed))
+        }
+        Command::Try { lesson, snippet } => {
+            let lesson = lesson::find(&lessons, &lesson)?;
+            let snippets = try_error::snippets(lesson)?;
+            let Some(name) = snippet else {
+                try_error::print_list(lesson, &snippets);
+                return Ok(ExitCode::SUCCESS);
+            };
+
+            let snippet = try_error::find(lesson, &snippets, &name)?;
+            let errors = try_error::compile(lesson, snippet)?;
+            try_error::print(lesson, snippet, &errors, &guide);
+            Ok(success_if(try_error::matches(snippet, &errors)))
+        }
+        Command::Book { out } => {
+            let out = out.unwrap_or_else(|| book::default_dir(&root));
+            let pages = book::generate(&lessons, &out)?;
+            println!(
+                "Wrote {} pages to {}; open {} to start reading.",
+                pages,
+                out.display(),
+                out.join("index.html").display()
+            );
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::Search { terms, limit } => {
+            let query = terms.join(" ");
+            let hits = search::Index::build(&lessons)?.search(&query);
+            search::print(&query, &hits, limit);
+            Ok(success_if(!hits.is_empty()))
+        }
+        Command::Glossary { term, check } => {
+            let glossary = glossary::Glossary::load(&lessons)?;
+            if check {
+                let problems = glossary.problems()?;
+                for problem in &problems {
+                    println!("{}", problem);
+                }
+                if problems.is_empty() {
+                    println!("Every term is defined before it's used.");
+                }
+                return Ok(success_if(problems.is_empty()));
+            }
+            if term.is_empty() {
+                glossary::print_list(&glossary);
+                return Ok(ExitCode::SUCCESS);
+            }
+
+            for (index, entry) in glossary.find(&term.join(" "))?.into_iter().enumerate() {
+                if index > 0 {
+                    println!();
+                }
+                glossary::print(&glossary, entry, &glossary.mentions(entry)?);
+            }
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::Tour { lesson } => {
+            let lesson = lesson::find(&lessons, &lesson)?;
+            let path = lesson.dir.join("src").join("main.rs");
+            let contents = fs::read_to_string(&path).map_err(|e| Error::io(&path, e))?;
+            let steps = tour::steps(&contents);
+
+            match tour::outputs(lesson, &steps, limits)? {
+                tour::Outputs::Failed(errors) => {
+                    guide.print_errors(&errors);
+                    Ok(ExitCode::FAILURE)
+                }
+                tour::Outputs::Printed {
+                    steps: printed,
+                    exceeded,
+                } => {
+                    tour::present(lesson, &steps, &printed, exceeded.as_ref());
+
+                    let mut progress = Progress::load(&root)?;
+                    progress.mark_viewed(lesson);
+                    progress.save(&root)?;
+                    Ok(ExitCode::SUCCESS)
+                }
+            }
+        }
+        Command::Browse => {
+            browse::browse(&root, &lessons, limits)?;
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::Serve { bind } => {
+            serve::serve(&root, &lessons, limits, &bind)?;
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::Snapshot { lesson, accept } => {
+            let selected = match lesson {
+                Some(query) => vec![lesson::find(&lessons, &query)?],
+                None => lessons.iter().collect(),
+            };
+
+            let mut passed = true;
+            for lesson in selected {
+                let snapshot = snapshot::verify(lesson, accept, limits)?;
+                snapshot::print(lesson, &snapshot, &guide);
+                passed &= snapshot.passed();
+            }
+
+            Ok(success_if(passed))
+        }
+    }
+}
+
+/// Checks every challenge and records each check as an attempt in the learner's progress.
+fn check_challenges(
+    root: &Path,
+    lesson: &Lesson,
+    challenges: &[String],
+    limits: &exec::Limits,
+    guide: &explain::Guide,
+) -> Result<bool> {
+    let mut progress = Progress::load(root)?;
+    let mut passed = true;
+    for challenge in challenges {
+        let report = check::check(lesson, challenge, limits)?;
+        check::print(lesson, challenge, &report, guide);
+        progress.record_attempt(lesson, challenge, report.passed());
+        passed &= report.passed();
+    }
+    progress.save(root)?;
+
+    Ok(passed)
+}
+
+fn list(lessons: &[Lesson]) {
+    let width = lessons
+        .iter()
+        .map(|lesson| lesson.metadata.title.chars().count())
+        .max()
+        .unwrap_or(0);
+
+    for lesson in lessons {
+        let mut notes = vec![format!("book {}", lesson.metadata.book_chapter)];
+        if !lesson.metadata.compiles {
+            notes.push("doesn't compile on purpose".to_string());
+        }
+        println!(
+            "{}  {:width$}  {} ({})",
+            lesson.id(),
+            lesson.metadata.title,
+            lesson.name,
+            notes.join(", ")
+        );
+
+        let challenge_width = lesson.challenges.iter().map(String::len).max().unwrap_or(0);
+        for challenge in &lesson.challenges {
+            println!(
+                "      {:challenge_width$}  {}",
+                challenge,
+                lesson.description(challenge)
+            );
+        }
+    }
+}
+
+fn success_if(passed: bool) -> ExitCode {
+    if passed {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
@@ -0,0 +1,143 @@
+//! The `[package.metadata.lesson]` table every lesson declares in its `Cargo.toml`:
+//!
+//! ```toml
+//! [package.metadata.lesson]
+//! title = "Control Flow"
+//! order = 4
+//! book-chapter = "3.5"
+//! prerequisites = ["functions"]
+//! compiles = true
+//!
+//! [[package.metadata.lesson.challenges]]
+//! name = "grader"
+//! description = "Turn points into a grade from 5 to 10."
+//! ```
+//!
+//! Cargo ignores it, so the lesson still builds on its own. Prerequisites refer to other lessons
+//! by package name, and every challenge must match a file in the lesson's `examples` directory.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::error::{Error, Result};
+use crate::lesson::Lesson;
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+pub struct Metadata {
+    pub title: String,
+    /// Where the lesson comes in the course. Lessons are listed and taken in this order.
+    pub order: u32,
+    /// The lessons to go through first, by package name.
+    #[serde(default)]
+    pub prerequisites: Vec<String>,
+    /// The chapter of The Rust Programming Language the lesson follows, e.g. `4.2`.
+    pub book_chapter: String,
+    /// `false` for lessons that intentionally don't compile, to show off the compiler's errors.
+    pub compiles: bool,
+    #[serde(default)]
+    pub challenges: Vec<ChallengeMetadata>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ChallengeMetadata {
+    /// The challenge's file in `examples`, without the `.rs`.
+    pub name: String,
+    pub description: String,
+}
+
+/// Checks a single lesson's metadata against its files, returning every problem found.
+pub fn problems(metadata: &Metadata, examples: &[String]) -> Vec<String> {
+    let mut problems = Vec::new();
+
+    if metadata.title.trim().is_empty() {
+        problems.push("`title` is empty".to_string());
+    }
+    let chapter_is_valid = metadata
+        .book_chapter
+        .split('.')
+        .all(|part| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit()));
+    if !chapter_is_valid {
+        problems.push(format!(
+            "`book-chapter` should look like `4.2`, not `{}`",
+            metadata.book_chapter
+        ));
+    }
+
+    let mut declared = BTreeSet::new();
+    for challenge in &metadata.challenges {
+        if !declared.insert(challenge.name.as_str()) {
+            problems.push(format!("challenge `{}` is listed twice", challenge.name));
+        }
+        if !examples.contains(&challenge.name) {
+            problems.push(format!(
+                "challenge `{}` has no examples/{}.rs",
+                challenge.name, challenge.name
+            ));
+        }
+        if challenge.description.trim().is_empty() {
+            problems.push(format!("challenge `{}` has no description", challenge.name));
+        }
+    }
+    for example in examples {
+        if !declared.contains(example.as_str()) {
+            problems.push(format!(
+                "examples/{}.rs isn't listed under `challenges`",
+                example
+            ));
+        }
+    }
+
+    problems
+}
+
+/// Checks what can only be checked across lessons: unique orders and prerequisites that exist
+/// and come earlier in the course.
+pub fn validate_course(lessons: &[Lesson]) -> Result<()> {
+    let orders: BTreeMap<&str, u32> = lessons
+        .iter()
+        .map(|lesson| (lesson.name.as_str(), lesson.metadata.order))
+        .collect();
+
+    for lesson in lessons {
+        let mut problems = Vec::new();
+
+        if let Some(other) = lessons.iter().find(|other| {
+            other.slug != lesson.slug && other.metadata.order == lesson.metadata.order
+        }) {
+            problems.push(format!(
+                "`order = {}` is also used by {}",
+                lesson.metadata.order, other.slug
+            ));
+        }
+        for prerequisite in &lesson.metadata.prerequisites {
+            match orders.get(prerequisite.as_str()) {
+                None => problems.push(format!("prerequisite `{}` is not a lesson", prerequisite)),
+                Some(&order) if order >= lesson.metadata.order => problems.push(format!(
+                    "prerequisite `{}` comes later in the course",
+                    prerequisite
+                )),
+                Some(_) => {}
+            }
+        }
+
+        check(&lesson.manifest_path(), problems)?;
+    }
+
+    Ok(())
+}
+
+/// Turns a list of problems into an error, if there are any.
+pub fn check(path: &Path, problems: Vec<String>) -> Result<()> {
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::Metadata {
+            path: path.to_path_buf(),
+            problems,
+        })
+    }
+}
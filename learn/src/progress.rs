@@ -0,0 +1,353 @@
+//! Keeps track of which lessons a learner has gone through and which challenges they've solved.
+//!
+//! Progress is stored in `.learn/progress.toml` at the root of the repo, so every learner's
+//! clone carries their own record.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, Result};
+use crate::lesson::Lesson;
+use crate::timestamp;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Progress {
+    /// Keyed by the lesson's directory name, e.g. `04-control-flow`.
+    #[serde(default)]
+    pub lessons: BTreeMap<String, LessonProgress>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct LessonProgress {
+    /// When the lesson was first run.
+    pub viewed_at: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub challenges: BTreeMap<String, ChallengeProgress>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ChallengeProgress {
+    /// How many times the challenge was checked, including the passing attempt.
+    pub attempts: u32,
+    pub last_attempt_at: Option<String>,
+    /// When the challenge was first checked successfully.
+    pub passed_at: Option<String>,
+    /// How many attempts it took to pass the first time.
+    pub attempts_to_pass: Option<u32>,
+    /// How many hints were revealed so far.
+    #[serde(default)]
+    pub hints_used: u32,
+    /// How many hints were revealed when the challenge was first passed. Zero means unaided.
+    pub hints_to_pass: Option<u32>,
+}
+
+pub fn path(root: &Path) -> PathBuf {
+    root.join(".learn").join("progress.toml")
+}
+
+impl Progress {
+    /// Loads the learner's progress, starting from scratch if nothing was recorded yet.
+    pub fn load(root: &Path) -> Result<Self> {
+        let path = path(root);
+        if !path.is_file() {
+            return Ok(Progress::default());
+        }
+
+        let contents = fs::read_to_string(&path).map_err(|e| Error::io(&path, e))?;
+        toml::from_str(&contents).map_err(|source| Error::Progress { path, source })
+    }
+
+    pub fn save(&self, root: &Path) -> Result<()> {
+        let path = path(root);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| Error::io(dir, e))?;
+        }
+
+        let contents = toml::to_string_pretty(self).map_err(Error::ProgressWrite)?;
+        fs::write(&path, contents).map_err(|e| Error::io(&path, e))
+    }
+
+    pub fn lesson(&self, lesson: &Lesson) -> Option<&LessonProgress> {
+        self.lessons.get(&lesson.slug)
+    }
+
+    fn lesson_mut(&mut self, lesson: &Lesson) -> &mut LessonProgress {
+        self.lessons.entry(lesson.slug.clone()).or_default()
+    }
+
+    pub fn mark_viewed(&mut self, lesson: &Lesson) {
+        let progress = self.lesson_mut(lesson);
+        if progress.viewed_at.is_none() {
+            progress.viewed_at = Some(timestamp::now());
+        }
+    }
+
+    pub fn record_attempt(&mut self, lesson: &Lesson, challenge: &str, passed: bool) {
+        let now = timestamp::now();
+        let progress = self
+            .lesson_mut(lesson)
+            .challenges
+            .entry(challenge.to_string())
+            .or_default();
+
+        progress.attempts += 1;
+        progress.last_attempt_at = Some(now.clone());
+        if passed && progress.passed_at.is_none() {
+            progress.passed_at = Some(now);
+            progress.attempts_to_pass = Some(progress.attempts);
+            progress.hints_to_pass = Some(progress.hints_used);
+        }
+    }
+
+    /// Reveals one more of the challenge's `available` hints and returns how many are revealed now.
+    pub fn reveal_hint(&mut self, lesson: &Lesson, challenge: &str, available: usize) -> usize {
+        let progress = self
+            .lesson_mut(lesson)
+            .challenges
+            .entry(challenge.to_string())
+            .or_default();
+
+        let revealed = (progress.hints_used as usize + 1).min(available);
+        progress.hints_used = revealed as u32;
+        revealed
+    }
+}
+
+impl LessonProgress {
+    pub fn challenge(&self, challenge: &str) -> Option<&ChallengeProgress> {
+        self.challenges.get(challenge)
+    }
+
+    pub fn passed(&self, challenge: &str) -> bool {
+        self.challenge(challenge)
+            .is_some_and(|progress| progress.passed_at.is_some())
+    }
+}
+
+/// Prints a checklist of every lesson, with the challenges nested under their lesson.
+pub fn print_status(lessons: &[Lesson], progress: &Progress) {
+    let width = lessons
+        .iter()
+        .map(|lesson| lesson.name.len())
+        .max()
+        .unwrap_or(0);
+    let mut viewed = 0;
+    let mut passed = 0;
+    let mut challenges = 0;
+
+    for lesson in lessons {
+        let recorded = progress.lesson(lesson);
+        let lesson_viewed = recorded.and_then(|recorded| recorded.viewed_at.as_deref());
+        let lesson_passed = passed_challenges(lesson, progress);
+
+        let mark = lesson_mark(lesson, progress);
+        let note = lesson_viewed
+            .map(|at| format!("viewed {}", timestamp::short(at)))
+            .unwrap_or_default();
+        let line = format!(
+            "[{}] {}  {:width$}  {}",
+            mark,
+            lesson.id(),
+            lesson.name,
+            note
+        );
+        println!("{}", line.trim_end());
+
+        for challenge in &lesson.challenges {
+            let mark = challenge_mark(lesson, challenge, progress);
+            let recorded = recorded.and_then(|recorded| recorded.challenge(challenge));
+            println!("      [{}] {}", mark, describe(challenge, recorded));
+        }
+
+        viewed += usize::from(lesson_viewed.is_some());
+        passed += lesson_passed;
+        challenges += lesson.challenges.len();
+    }
+
+    println!();
+    println!(
+        "{}/{} lessons viewed, {}/{} challenges passed",
+        viewed,
+        lessons.len(),
+        passed,
+        challenges
+    );
+}
+
+fn passed_challenges(lesson: &Lesson, progress: &Progress) -> usize {
+    let recorded = progress.lesson(lesson);
+    lesson
+        .challenges
+        .iter()
+        .filter(|challenge| recorded.is_some_and(|recorded| recorded.passed(challenge)))
+        .count()
+}
+
+/// `x` once the lesson was viewed and all of its challenges passed, `~` when it was started, and
+/// a space before that.
+pub fn lesson_mark(lesson: &Lesson, progress: &Progress) -> &'static str {
+    let viewed = progress
+        .lesson(lesson)
+        .is_some_and(|recorded| recorded.viewed_at.is_some());
+    let passed = passed_challenges(lesson, progress);
+
+    match (viewed, passed == lesson.challenges.len()) {
+        (true, true) => "x",
+        (false, _) if passed == 0 => " ",
+        _ => "~",
+    }
+}
+
+/// `x` once the challenge passed, a space before that.
+pub fn challenge_mark(lesson: &Lesson, challenge: &str, progress: &Progress) -> &'static str {
+    let passed = progress
+        .lesson(lesson)
+        .and_then(|recorded| recorded.challenge(challenge))
+        .is_some_and(|recorded| recorded.passed_at.is_some());
+    if passed {
+        "x"
+    } else {
+        " "
+    }
+}
+
+fn describe(challenge: &str, progress: Option<&ChallengeProgress>) -> String {
+    let Some(progress) = progress else {
+        return challenge.to_string();
+    };
+
+    match &progress.passed_at {
+        Some(at) => format!(
+            "{}  passed {} after {}, {}",
+            challenge,
+            timestamp::short(at),
+            attempts(progress.attempts_to_pass.unwrap_or(progress.attempts)),
+            hints(progress.hints_to_pass.unwrap_or(progress.hints_used))
+        ),
+        None if progress.hints_used > 0 => format!(
+            "{}  {}, {} revealed",
+            challenge,
+            attempts(progress.attempts),
+            hints(progress.hints_used)
+        ),
+        None => format!("{}  {}", challenge, attempts(progress.attempts)),
+    }
+}
+
+fn hints(count: u32) -> String {
+    match count {
+        0 => "unaided".to_string(),
+        1 => "1 hint".to_string(),
+        n => format!("{} hints", n),
+    }
+}
+
+fn attempts(count: u32) -> String {
+    match count {
+        1 => "1 attempt".to_string(),
+        n => format!("{} attempts", n),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lesson;
+
+    fn control_flow() -> Lesson {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+        let lessons = lesson::discover(root).unwrap();
+        lesson::find(&lessons, "04").unwrap().clone()
+    }
+
+    fn challenge<'a>(progress: &'a Progress, lesson: &Lesson) -> &'a ChallengeProgress {
+        progress
+            .lesson(lesson)
+            .unwrap()
+            .challenge("grader")
+            .unwrap()
+    }
+
+    #[test]
+    fn every_check_is_an_attempt_and_the_first_pass_is_kept() {
+        let lesson = control_flow();
+        let mut progress = Progress::default();
+        progress.record_attempt(&lesson, "grader", false);
+        progress.record_attempt(&lesson, "grader", false);
+        assert!(!progress.lesson(&lesson).unwrap().passed("grader"));
+
+        progress.record_attempt(&lesson, "grader", true);
+        let passed_at = challenge(&progress, &lesson).passed_at.clone();
+        progress.record_attempt(&lesson, "grader", true);
+
+        let recorded = challenge(&progress, &lesson);
+        assert_eq!(recorded.attempts, 4);
+        assert_eq!(recorded.attempts_to_pass, Some(3));
+        assert_eq!(recorded.passed_at, passed_at);
+        assert!(progress.lesson(&lesson).unwrap().passed("grader"));
+        assert!(!progress.lesson(&lesson).unwrap().passed("factorial"));
+    }
+
+    #[test]
+    fn hints_to_pass_are_the_ones_revealed_before_the_first_pass() {
+        let lesson = control_flow();
+        let mut progress = Progress::default();
+        assert_eq!(progress.reveal_hint(&lesson, "grader", 3), 1);
+        assert_eq!(progress.reveal_hint(&lesson, "grader", 3), 2);
+        progress.record_attempt(&lesson, "grader", true);
+        assert_eq!(progress.reveal_hint(&lesson, "grader", 3), 3);
+        assert_eq!(progress.reveal_hint(&lesson, "grader", 3), 3);
+
+        let recorded = challenge(&progress, &lesson);
+        assert_eq!(recorded.hints_used, 3);
+        assert_eq!(recorded.hints_to_pass, Some(2));
+    }
+
+    #[test]
+    fn unaided_passes_and_open_challenges_are_described() {
+        let lesson = control_flow();
+        let mut progress = Progress::default();
+        progress.record_attempt(&lesson, "grader", false);
+        progress.reveal_hint(&lesson, "grader", 3);
+        progress.record_attempt(&lesson, "grader", false);
+        assert_eq!(
+            describe("grader", Some(challenge(&progress, &lesson))),
+            "grader  2 attempts, 1 hint revealed"
+        );
+        assert_eq!(describe("grader", None), "grader");
+
+        progress.record_attempt(&lesson, "factorial", true);
+        let recorded = progress.lesson(&lesson).unwrap().challenge("factorial");
+        assert!(describe("factorial", recorded).ends_with(" after 1 attempt, unaided"));
+    }
+
+    #[test]
+    fn progress_is_saved_and_loaded_again() {
+        let lesson = control_flow();
+        let root = std::env::temp_dir().join(format!("learn-progress-{}", std::process::id()));
+        assert!(Progress::load(&root).unwrap().lessons.is_empty());
+
+        let mut progress = Progress::default();
+        progress.mark_viewed(&lesson);
+        progress.reveal_hint(&lesson, "grader", 3);
+        progress.record_attempt(&lesson, "grader", false);
+        progress.record_attempt(&lesson, "grader", true);
+        progress.save(&root).unwrap();
+
+        let loaded = Progress::load(&root).unwrap();
+        let _ = fs::remove_dir_all(&root);
+        let recorded = challenge(&loaded, &lesson);
+        assert!(loaded.lesson(&lesson).unwrap().viewed_at.is_some());
+        assert_eq!(recorded.attempts, 2);
+        assert_eq!(recorded.attempts_to_pass, Some(2));
+        assert_eq!(recorded.hints_to_pass, Some(1));
+        assert_eq!(
+            recorded.passed_at,
+            challenge(&progress, &lesson).passed_at,
+            "passed_at"
+        );
+    }
+}
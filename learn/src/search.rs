@@ -0,0 +1,296 @@
+//! Full-text search over what the lessons say and do.
+//!
+//! Every lesson's `src/main.rs` and challenges are split into passages: a paragraph of a comment
+//! block, a `// ===== TOPIC =====` heading, or a run of code between blank lines. A passage matches
+//! when it contains every search term, as a word or the start of one, so `shadow` finds
+//! "shadowing". Matches are ranked by how rare and how frequent the terms are, with the words
+//! appearing side by side, headings, and explanations counting for more than code.
+
+use std::collections::HashMap;
+use std::fs;
+use std::io::IsTerminal;
+use std::path::PathBuf;
+
+use crate::comments;
+use crate::error::{Error, Result};
+use crate::lesson::Lesson;
+use crate::source;
+
+/// How many lines of a passage are shown, starting with the first one that matches.
+const EXCERPT_LINES: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Heading,
+    Prose,
+    Code,
+}
+
+impl Kind {
+    fn weight(self) -> f64 {
+        match self {
+            Kind::Heading => 3.0,
+            Kind::Prose => 1.5,
+            Kind::Code => 1.0,
+        }
+    }
+}
+
+struct Passage {
+    /// Position of the lesson in the course.
+    lesson: usize,
+    /// Relative to the lesson's directory, e.g. `examples/grader.rs`.
+    file: PathBuf,
+    kind: Kind,
+    /// The 1-based line of `lines[0]`.
+    line: usize,
+    /// The text of each line, without comment syntax.
+    lines: Vec<String>,
+    words: Vec<String>,
+}
+
+/// Every passage of every lesson, ready to be searched.
+pub struct Index<'a> {
+    lessons: &'a [Lesson],
+    passages: Vec<Passage>,
+}
+
+pub struct Hit<'a> {
+    pub lesson: &'a Lesson,
+    pub file: PathBuf,
+    /// The 1-based line of the first line that matches.
+    pub line: usize,
+    pub excerpt: Vec<String>,
+}
+
+impl<'a> Index<'a> {
+    pub fn build(lessons: &'a [Lesson]) -> Result<Self> {
+        let mut passages = Vec::new();
+        for (position, lesson) in lessons.iter().enumerate() {
+            let files = std::iter::once(PathBuf::from("src/main.rs")).chain(
+                lesson
+                    .challenges
+                    .iter()
+                    .map(|challenge| PathBuf::from(format!("examples/{}.rs", challenge))),
+            );
+            for file in files {
+                let path = lesson.dir.join(&file);
+                let contents = fs::read_to_string(&path).map_err(|e| Error::io(&path, e))?;
+                passages.extend(
+                    split(&contents)
+                        .into_iter()
+                        .map(|(kind, line, lines)| Passage {
+                            lesson: position,
+                            file: file.clone(),
+                            kind,
+                            line,
+                            words: lines.iter().flat_map(|line| words(line)).collect(),
+                            lines,
+                        }),
+                );
+            }
+        }
+
+        Ok(Index { lessons, passages })
+    }
+
+    /// The passages containing every term of `query`, best first.
+    pub fn search(&self, query: &str) -> Vec<Hit<'a>> {
+        let terms = words(query);
+        if terms.is_empty() {
+            return Vec::new();
+        }
+
+        let mut frequency: HashMap<&str, usize> = HashMap::new();
+        for term in &terms {
+            let count = self
+                .passages
+                .iter()
+                .filter(|passage| occurrences(&passage.words, term) > 0)
+                .count();
+            frequency.insert(term, count);
+        }
+
+        let total = self.passages.len() as f64;
+        let mut hits = Vec::new();
+        for passage in &self.passages {
+            let mut score = 0.0;
+            for term in &terms {
+                let count = occurrences(&passage.words, term);
+                if count == 0 {
+                    score = 0.0;
+                    break;
+                }
+                let rarity = (1.0 + total / frequency[term.as_str()] as f64).ln();
+                score += (1.0 + (count as f64).ln()) * rarity;
+            }
+            if score == 0.0 {
+                continue;
+            }
+            if terms.len() > 1 && adjacent(&passage.words, &terms) {
+                score *= 2.0;
+            }
+            score *= passage.kind.weight();
+
+            let first = passage
+                .lines
+                .iter()
+                .position(|line| terms.iter().any(|term| occurrences(&words(line), term) > 0))
+                .unwrap_or(0);
+            hits.push((passage, first, score));
+        }
+
+        hits.sort_by(|a, b| {
+            b.2.total_cmp(&a.2)
+                .then(a.0.lesson.cmp(&b.0.lesson))
+                .then(a.0.file.cmp(&b.0.file))
+                .then(a.0.line.cmp(&b.0.line))
+        });
+
+        hits.into_iter()
+            .map(|(passage, first, _)| Hit {
+                lesson: &self.lessons[passage.lesson],
+                file: passage.file.clone(),
+                line: passage.line + first,
+                excerpt: passage.lines[first..]
+                    .iter()
+                    .take(EXCERPT_LINES)
+                    .cloned()
+                    .collect(),
+            })
+            .collect()
+    }
+}
+
+/// Splits a source file into passages: their kind, first line, and text.
+pub fn split(contents: &str) -> Vec<(Kind, usize, Vec<String>)> {
+    let mut passages = Vec::new();
+    let mut current: Option<(Kind, usize, Vec<String>)> = None;
+    let mut in_block = false;
+
+    for (index, line) in contents.lines().enumerate() {
+        let trimmed = line.trim();
+        let (kind, text) = if in_block || trimmed.starts_with("/*") {
+            in_block = !trimmed.ends_with("*/");
+            (Kind::Prose, comments::text(&[line]).concat())
+        } else if comments::is_tag(line) {
+            continue;
+        } else if let Some(heading) = source::heading(trimmed) {
+            (Kind::Heading, heading)
+        } else if trimmed.starts_with("//") {
+            (Kind::Prose, comments::text(&[line]).concat())
+        } else {
+            (Kind::Code, trimmed.to_string())
+        };
+
+        let continues = current
+            .as_ref()
+            .is_some_and(|(current, _, _)| *current == kind && kind != Kind::Heading);
+        if !continues || text.is_empty() {
+            passages.extend(current.take());
+        }
+        if !text.is_empty() && !matches!(text.as_str(), "{" | "}" | "};") {
+            current
+                .get_or_insert_with(|| (kind, index + 1, Vec::new()))
+                .2
+                .push(text);
+        }
+    }
+    passages.extend(current);
+
+    passages
+}
+
+/// The lowercase words of `text`; `snake_case` names are split into their parts too.
+pub fn words(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// How many of `words` start with `term`.
+fn occurrences(words: &[String], term: &str) -> usize {
+    words.iter().filter(|word| word.starts_with(term)).count()
+}
+
+/// Whether the terms appear one right after the other, in order.
+fn adjacent(words: &[String], terms: &[String]) -> bool {
+    words.windows(terms.len()).any(|window| {
+        window
+            .iter()
+            .zip(terms)
+            .all(|(word, term)| word.starts_with(term.as_str()))
+    })
+}
+
+pub fn print(query: &str, hits: &[Hit], limit: usize) {
+    if hits.is_empty() {
+        println!("No matches for \"{}\".", query);
+        return;
+    }
+
+    let terms = words(query);
+    let color = std::io::stdout().is_terminal();
+    for hit in hits.iter().take(limit) {
+        println!(
+            "{} {}, {}:{}",
+            hit.lesson.id(),
+            hit.lesson.metadata.title,
+            hit.file.display(),
+            hit.line
+        );
+        for line in &hit.excerpt {
+            println!("  > {}", emphasize(line, &terms, color));
+        }
+        println!();
+    }
+
+    if hits.len() > limit {
+        println!(
+            "Showing the best {} of {} matches; use --limit to see more.",
+            limit,
+            hits.len()
+        );
+    } else {
+        println!(
+            "{} match{}.",
+            hits.len(),
+            if hits.len() == 1 { "" } else { "es" }
+        );
+    }
+}
+
+/// Marks the words that start with a search term, in bold yellow on a terminal and with
+/// `[brackets]` otherwise.
+fn emphasize(line: &str, terms: &[String], color: bool) -> String {
+    let (open, close) = if color {
+        ("\x1b[1;33m", "\x1b[0m")
+    } else {
+        ("[", "]")
+    };
+
+    let mut result = String::new();
+    let mut rest = line;
+    while let Some(start) = rest.find(char::is_alphanumeric) {
+        let (before, from_word) = rest.split_at(start);
+        let end = from_word
+            .find(|c: char| !c.is_alphanumeric())
+            .unwrap_or(from_word.len());
+        let (word, after) = from_word.split_at(end);
+
+        result.push_str(before);
+        let lower = word.to_lowercase();
+        if terms.iter().any(|term| lower.starts_with(term.as_str())) {
+            result.push_str(open);
+            result.push_str(word);
+            result.push_str(close);
+        } else {
+            result.push_str(word);
+        }
+        rest = after;
+    }
+    result.push_str(rest);
+
+    result
+}
@@ -0,0 +1,577 @@
+//! A local web playground for workshops: serves the book's pages, with every challenge in an
+//! editor, and runs and checks what attendees send on the host.
+//!
+//! Nothing is loaded from the internet, so it works on a LAN without one. Every run happens in a
+//! throwaway copy of the lessons through the same `learn run` and `learn check` commands as in the
+//! terminal, so the limits hold, hidden tests stay on the host, and nobody's edits or progress
+//! touch the repo.
+//!
+//! What attendees send runs with the host user's permissions, so runs are only taken from the
+//! playground's own pages: the Host header has to name the address the server is bound to, so a
+//! site pointing its own domain at the host is turned away, and every run has to carry a header
+//! that pages on other sites can't send without the browser asking first, which is never allowed.
+
+use std::fs;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{IpAddr, SocketAddr, TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::Duration;
+
+use crate::book::{self, Runner};
+use crate::copy::copy_dir;
+use crate::error::{Error, Result};
+use crate::exec::Limits;
+use crate::lesson::{self, Lesson};
+use crate::tour;
+
+const SCRIPT: &str = include_str!("../support/playground.js");
+
+/// The largest challenge source accepted, which is plenty for any solution.
+const MAX_BODY: usize = 256 * 1024;
+
+/// The longest request line or header accepted, and the most headers.
+const MAX_LINE: usize = 8 * 1024;
+const MAX_HEADERS: usize = 64;
+
+/// How long a connection may go without sending anything while its request is read, and the most
+/// connections open at once, so visitors that never finish a request can't use up the threads.
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+const MAX_CONNECTIONS: usize = 64;
+
+/// The header `playground.js` sends with every run and check.
+const PLAYGROUND_HEADER: &str = "x-learn-playground";
+
+struct Server<'a> {
+    root: &'a Path,
+    lessons: &'a [Lesson],
+    limits: &'a Limits,
+    /// The address the server is bound to, which requests have to name as their Host.
+    address: SocketAddr,
+    /// Numbers the scratch copies, so simultaneous runs don't share one.
+    runs: AtomicUsize,
+    /// The runs in progress, and how many there can be at once.
+    running: AtomicUsize,
+    max_running: usize,
+    /// The connections open now.
+    connections: AtomicUsize,
+}
+
+struct Request {
+    method: String,
+    path: String,
+    query: Vec<(String, String)>,
+    /// With lowercase names.
+    headers: Vec<(String, String)>,
+    body: String,
+}
+
+/// A run or connection in progress, counted in `Server::running` or `Server::connections` until
+/// it's dropped.
+struct Slot<'a>(&'a AtomicUsize);
+
+pub fn serve(root: &Path, lessons: &[Lesson], limits: &Limits, bind: &str) -> Result<()> {
+    let listener = TcpListener::bind(bind).map_err(|source| Error::Bind {
+        address: bind.to_string(),
+        source,
+    })?;
+    let address = listener.local_addr().map_err(|source| Error::Bind {
+        address: bind.to_string(),
+        source,
+    })?;
+
+    println!(
+        "Serving {} lessons at http://{} (Ctrl+C to stop)",
+        lessons.len(),
+        address
+    );
+    if address.ip().is_loopback() {
+        println!(
+            "Only this computer can reach it; use --bind 0.0.0.0:{} to share it on your network.",
+            address.port()
+        );
+    } else if address.ip().is_unspecified() {
+        eprintln!(
+            "warning: everyone who can reach port {} of this computer can run code on it, \
+             with your user's permissions; only share it on a network you trust",
+            address.port()
+        );
+    }
+
+    let server = Server {
+        root,
+        lessons,
+        limits,
+        address,
+        runs: AtomicUsize::new(0),
+        running: AtomicUsize::new(0),
+        max_running: thread::available_parallelism().map_or(2, |cores| cores.get()),
+        connections: AtomicUsize::new(0),
+    };
+    thread::scope(|scope| {
+        for mut stream in listener.incoming().flatten() {
+            let server = &server;
+            let Some(slot) = Slot::take(&server.connections, MAX_CONNECTIONS) else {
+                let _ = respond(
+                    &mut stream,
+                    "503 Service Unavailable",
+                    "text/plain",
+                    "Too many connections at once; try again in a moment.",
+                );
+                continue;
+            };
+            scope.spawn(move || {
+                let _slot = slot;
+                // A visitor closing the tab mid-run is nothing to report.
+                let _ = server.handle(stream);
+            });
+        }
+    });
+
+    Ok(())
+}
+
+impl Server<'_> {
+    fn handle(&self, mut stream: TcpStream) -> io::Result<()> {
+        stream.set_read_timeout(Some(READ_TIMEOUT))?;
+        let request = match read_request(&mut stream)? {
+            Ok(request) => request,
+            Err((status, message)) => return respond(&mut stream, status, "text/plain", message),
+        };
+        if !request
+            .header("host")
+            .is_some_and(|host| is_bound_address(host, self.address))
+        {
+            return respond(
+                &mut stream,
+                "421 Misdirected Request",
+                "text/plain",
+                "Open the playground by the host's IP address, not a domain name.",
+            );
+        }
+
+        match (request.method.as_str(), request.path.as_str()) {
+            ("GET", "/" | "/index.html") => {
+                let html = book::index(self.lessons, Runner::Local);
+                respond(&mut stream, "200 OK", "text/html", &html)
+            }
+            ("GET", "/style.css") => respond(&mut stream, "200 OK", "text/css", book::STYLE),
+            ("GET", "/playground.js") => respond(&mut stream, "200 OK", "text/javascript", SCRIPT),
+            ("GET", page) => {
+                let lesson = self
+                    .lessons
+                    .iter()
+                    .find(|lesson| page.strip_prefix('/') == Some(&book::page_name(lesson)));
+                match lesson.map(|lesson| book::page(self.lessons, lesson, Runner::Local)) {
+                    Some(Ok(html)) => respond(&mut stream, "200 OK", "text/html", &html),
+                    Some(Err(err)) => respond(
+                        &mut stream,
+                        "500 Internal Server Error",
+                        "text/plain",
+                        &err.to_string(),
+                    ),
+                    None => respond(&mut stream, "404 Not Found", "text/plain", "No such page."),
+                }
+            }
+            ("POST", "/run" | "/check") => self.run(&mut stream, &request),
+            _ => respond(
+                &mut stream,
+                "405 Method Not Allowed",
+                "text/plain",
+                "Only GET and POST are supported.",
+            ),
+        }
+    }
+
+    /// Runs or checks the lesson or challenge in a scratch copy, streaming what `learn` prints.
+    fn run(&self, stream: &mut TcpStream, request: &Request) -> io::Result<()> {
+        // The Host is already known to be ours, so the playground's own pages send it as Origin.
+        let own = request
+            .header("host")
+            .map(|host| format!("http://{}", host));
+        let foreign = request
+            .header("origin")
+            .is_some_and(|origin| Some(origin) != own.as_deref());
+        if foreign || request.header(PLAYGROUND_HEADER).is_none() {
+            return respond(
+                stream,
+                "403 Forbidden",
+                "text/plain",
+                "Only the playground's own pages can run code.",
+            );
+        }
+        let Some(_slot) = Slot::take(&self.running, self.max_running) else {
+            return respond(
+                stream,
+                "503 Service Unavailable",
+                "text/plain",
+                "Too many runs at once; try again in a moment.",
+            );
+        };
+
+        let (lesson, args, focused) = match self.command(request) {
+            Ok(command) => command,
+            Err(err) => return respond(stream, "400 Bad Request", "text/plain", &err.to_string()),
+        };
+
+        let scratch = self
+            .root
+            .join("target")
+            .join("learn")
+            .join("serve")
+            .join(self.runs.fetch_add(1, Ordering::Relaxed).to_string());
+        let result = self.run_in(stream, &scratch, lesson, request, &args, focused.as_deref());
+        let _ = fs::remove_dir_all(&scratch);
+
+        result
+    }
+
+    /// The lesson to run, the `learn` arguments that run it, and for a single snippet, the
+    /// lesson's `src/main.rs` that only shows what the snippet prints.
+    fn command(&self, request: &Request) -> Result<(&Lesson, Vec<String>, Option<String>)> {
+        let parameter = |name: &str| {
+            request
+                .query
+                .iter()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.as_str())
+        };
+
+        let lesson = lesson::find(self.lessons, parameter("lesson").unwrap_or_default())?;
+        let challenge = parameter("challenge")
+            .map(|challenge| lesson.challenge(challenge))
+            .transpose()?;
+        let focused = parameter("step")
+            .map(|step| focused(lesson, step))
+            .transpose()?;
+
+        let mut args = self.limits.args();
+        match (request.path.as_str(), challenge) {
+            ("/check", Some(challenge)) => {
+                args.extend(["check".to_string(), lesson.id(), challenge.to_string()])
+            }
+            ("/check", None) => args.extend(["check".to_string(), lesson.id()]),
+            (_, Some(challenge)) => args.extend([
+                "run".to_string(),
+                lesson.id(),
+                "--example".to_string(),
+                challenge.to_string(),
+            ]),
+            (_, None) => {
+                args.extend(["run".to_string(), lesson.id()]);
+                if parameter("fixed") == Some("true") {
+                    args.push("--fixed".to_string());
+                }
+            }
+        }
+
+        Ok((lesson, args, focused))
+    }
+
+    fn run_in(
+        &self,
+        stream: &mut TcpStream,
+        scratch: &Path,
+        lesson: &Lesson,
+        request: &Request,
+        args: &[String],
+        focused: Option<&str>,
+    ) -> io::Result<()> {
+        copy_dir(&self.root.join("lessons"), &scratch.join("lessons"))?;
+        if let Some(focused) = focused {
+            let path = scratch
+                .join("lessons")
+                .join(&lesson.slug)
+                .join("src")
+                .join("main.rs");
+            fs::write(path, focused)?;
+        }
+        if let Some((_, challenge)) = request.query.iter().find(|(key, _)| key == "challenge") {
+            if !request.body.trim().is_empty() {
+                let path = scratch
+                    .join("lessons")
+                    .join(&lesson.slug)
+                    .join("examples")
+                    .join(format!("{}.rs", challenge));
+                fs::write(path, &request.body)?;
+            }
+        }
+
+        let program = std::env::current_exe().unwrap_or_else(|_| PathBuf::from("learn"));
+        let mut child = Command::new(program)
+            .args(args)
+            .current_dir(scratch)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+
+        let mut stderr = child.stderr.take();
+        let errors = thread::spawn(move || {
+            let mut errors = String::new();
+            if let Some(stderr) = &mut stderr {
+                let _ = stderr.read_to_string(&mut errors);
+            }
+            errors
+        });
+
+        stream.write_all(
+            b"HTTP/1.1 200 OK\r\nContent-Type: text/plain; charset=utf-8\r\n\
+              X-Content-Type-Options: nosniff\r\nCache-Control: no-store\r\n\
+              Transfer-Encoding: chunked\r\nConnection: close\r\n\r\n",
+        )?;
+
+        let mut stdout = child.stdout.take();
+        let mut buffer = [0; 4096];
+        let streamed: io::Result<()> = (|| {
+            while let Some(stdout) = &mut stdout {
+                let read = stdout.read(&mut buffer)?;
+                if read == 0 {
+                    break;
+                }
+                write_chunk(stream, &buffer[..read])?;
+            }
+            Ok(())
+        })();
+        if streamed.is_err() {
+            // The visitor went away; don't keep building and running for nobody.
+            let _ = child.kill();
+        }
+        let _ = child.wait();
+        streamed?;
+
+        let errors = errors.join().unwrap_or_default();
+        write_chunk(stream, errors.as_bytes())?;
+        stream.write_all(b"0\r\n\r\n")
+    }
+}
+
+impl Request {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+impl<'a> Slot<'a> {
+    /// Counts another run, unless there are `max` already.
+    fn take(running: &'a AtomicUsize, max: usize) -> Option<Self> {
+        running
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |count| {
+                (count < max).then_some(count + 1)
+            })
+            .ok()
+            .map(|_| Slot(running))
+    }
+}
+
+impl Drop for Slot<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+/// The lesson's `src/main.rs` with only the output of the snippet at `step` shown.
+fn focused(lesson: &Lesson, step: &str) -> Result<String> {
+    let path = lesson.dir.join("src").join("main.rs");
+    let contents = fs::read_to_string(&path).map_err(|e| Error::io(&path, e))?;
+    let steps = tour::steps(&contents);
+    match step.parse::<usize>() {
+        Ok(index) if steps.get(index).is_some_and(tour::Step::runs_alone) => {
+            Ok(tour::focused(&contents, &steps, index))
+        }
+        _ => Err(Error::StepNotFound {
+            lesson: lesson.id(),
+            step: step.to_string(),
+        }),
+    }
+}
+
+/// Whether a request's Host header names the address the server is bound to, with its port, or
+/// `localhost` for a loopback one. A page that points its own domain at the host sends that domain.
+fn is_bound_address(host: &str, bound: SocketAddr) -> bool {
+    let (name, port) = match host.rsplit_once(':') {
+        Some((name, port)) if !port.contains(']') => (name, port.parse().ok()),
+        _ => (host, Some(80)),
+    };
+    if port != Some(bound.port()) {
+        return false;
+    }
+    if name.eq_ignore_ascii_case("localhost") {
+        return bound.ip().is_loopback() || bound.ip().is_unspecified();
+    }
+    match name
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .parse::<IpAddr>()
+    {
+        Ok(ip) => bound.ip().is_unspecified() || ip == bound.ip(),
+        Err(_) => false,
+    }
+}
+
+/// Reads a line of at most `MAX_LINE` bytes, or `None` when it's longer.
+fn read_line(reader: &mut impl BufRead) -> io::Result<Option<String>> {
+    let mut line = String::new();
+    reader.take(MAX_LINE as u64 + 1).read_line(&mut line)?;
+    Ok((line.len() <= MAX_LINE).then_some(line))
+}
+
+/// Reads the request line, headers and body, or the status and message to turn it down with.
+fn read_request(
+    stream: &mut TcpStream,
+) -> io::Result<std::result::Result<Request, (&'static str, &'static str)>> {
+    let mut reader = BufReader::new(stream);
+    let Some(line) = read_line(&mut reader)? else {
+        return Ok(Err(("414 URI Too Long", "That address is too long.")));
+    };
+    let mut parts = line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        return Ok(Err(("400 Bad Request", "Malformed request.")));
+    };
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+
+    let mut headers = Vec::new();
+    loop {
+        let Some(header) = read_line(&mut reader)? else {
+            return Ok(Err((
+                "431 Request Header Fields Too Large",
+                "A header is too long.",
+            )));
+        };
+        if header.trim().is_empty() {
+            break;
+        }
+        if headers.len() == MAX_HEADERS {
+            return Ok(Err((
+                "431 Request Header Fields Too Large",
+                "Too many headers.",
+            )));
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            headers.push((name.trim().to_ascii_lowercase(), value.trim().to_string()));
+        }
+    }
+    let length = headers
+        .iter()
+        .find(|(name, _)| name == "content-length")
+        .map_or(0, |(_, value)| value.parse().unwrap_or(0));
+    if length > MAX_BODY {
+        return Ok(Err(("413 Payload Too Large", "That's too much code.")));
+    }
+
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body)?;
+
+    Ok(Ok(Request {
+        method: method.to_string(),
+        path: decode(path),
+        query: query
+            .split('&')
+            .filter_map(|pair| pair.split_once('='))
+            .map(|(key, value)| (decode(key), decode(value)))
+            .collect(),
+        headers,
+        body: String::from_utf8_lossy(&body).into_owned(),
+    }))
+}
+
+/// Undoes the percent-encoding of a URL's path or query.
+fn decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => decoded.push(b' '),
+            b'%' if i + 2 < bytes.len() => {
+                let hex = |byte: u8| (byte as char).to_digit(16);
+                match (hex(bytes[i + 1]), hex(bytes[i + 2])) {
+                    (Some(high), Some(low)) => {
+                        decoded.push((high * 16 + low) as u8);
+                        i += 2;
+                    }
+                    _ => decoded.push(b'%'),
+                }
+            }
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+fn respond(stream: &mut TcpStream, status: &str, content_type: &str, body: &str) -> io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}; charset=utf-8\r\nContent-Length: {}\r\n\
+         Connection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    )
+}
+
+fn write_chunk(stream: &mut TcpStream, data: &[u8]) -> io::Result<()> {
+    if data.is_empty() {
+        return Ok(());
+    }
+    write!(stream, "{:x}\r\n", data.len())?;
+    stream.write_all(data)?;
+    stream.write_all(b"\r\n")?;
+    stream.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn the_host_has_to_name_the_bound_address() {
+        let loopback: SocketAddr = "127.0.0.1:8000".parse().unwrap();
+        assert!(is_bound_address("127.0.0.1:8000", loopback));
+        assert!(is_bound_address("localhost:8000", loopback));
+        assert!(is_bound_address("LOCALHOST:8000", loopback));
+        assert!(!is_bound_address("127.0.0.1:8001", loopback));
+        assert!(!is_bound_address("127.0.0.1", loopback));
+        assert!(!is_bound_address("192.168.1.10:8000", loopback));
+        assert!(!is_bound_address("attacker.example:8000", loopback));
+        assert!(!is_bound_address("", loopback));
+
+        let everywhere: SocketAddr = "0.0.0.0:8000".parse().unwrap();
+        assert!(is_bound_address("192.168.1.10:8000", everywhere));
+        assert!(is_bound_address("localhost:8000", everywhere));
+        assert!(!is_bound_address("learn.example:8000", everywhere));
+
+        let lan: SocketAddr = "192.168.1.10:8000".parse().unwrap();
+        assert!(is_bound_address("192.168.1.10:8000", lan));
+        assert!(!is_bound_address("localhost:8000", lan));
+    }
+
+    #[test]
+    fn the_port_can_be_left_out_on_80_and_ipv6_is_bracketed() {
+        let http: SocketAddr = "10.0.0.1:80".parse().unwrap();
+        assert!(is_bound_address("10.0.0.1", http));
+        assert!(is_bound_address("10.0.0.1:80", http));
+
+        let ipv6: SocketAddr = "[::1]:8000".parse().unwrap();
+        assert!(is_bound_address("[::1]:8000", ipv6));
+        assert!(!is_bound_address("[::1]", ipv6));
+        assert!(!is_bound_address("[::2]:8000", ipv6));
+    }
+
+    #[test]
+    fn runs_at_once_are_capped() {
+        let running = AtomicUsize::new(0);
+        let first = Slot::take(&running, 2).unwrap();
+        let _second = Slot::take(&running, 2).unwrap();
+        assert!(Slot::take(&running, 2).is_none());
+        drop(first);
+        assert!(Slot::take(&running, 2).is_some());
+    }
+}
@@ -0,0 +1,155 @@
+//! Golden-output snapshots of what every lesson's `main` prints.
+//!
+//! Each lesson that compiles keeps its expected stdout in `snapshots/main.txt`, so an edit
+//! to a lesson can't silently change what it demonstrates. Lessons that only compile with
+//! their fixes applied are snapshotted with the `fixed` feature, in `snapshots/main-fixed.txt`.
+//! Lessons that don't compile at all have nothing to snapshot.
+
+use std::fs;
+use std::path::PathBuf;
+
+use crate::check::Outcome;
+use crate::error::{Error, Result};
+use crate::exec::Limits;
+use crate::explain::Guide;
+use crate::lesson::Lesson;
+use crate::{diagnostics, diff, exec};
+
+pub const DIR: &str = "snapshots";
+
+pub enum Snapshot {
+    /// The lesson intentionally doesn't compile and has no fixed variant.
+    Skipped,
+    /// There's no snapshot yet, and it wasn't asked to be saved.
+    Missing,
+    /// The snapshot was written, either for the first time or over one that no longer matched.
+    Saved {
+        previous: Option<String>,
+        current: String,
+    },
+    Checked(Outcome),
+}
+
+impl Snapshot {
+    pub fn passed(&self) -> bool {
+        match self {
+            Snapshot::Skipped | Snapshot::Saved { .. } => true,
+            Snapshot::Missing => false,
+            Snapshot::Checked(outcome) => matches!(outcome, Outcome::Passed),
+        }
+    }
+}
+
+/// Where the lesson's snapshot lives, depending on whether it's built with its fixes.
+pub fn path(lesson: &Lesson) -> PathBuf {
+    let name = if lesson.has_fixed_variant {
+        "main-fixed.txt"
+    } else {
+        "main.txt"
+    };
+    lesson.dir.join(DIR).join(name)
+}
+
+/// Runs the lesson and compares its output with the snapshot, or saves it when `accept` is set.
+pub fn verify(lesson: &Lesson, accept: bool, limits: &Limits) -> Result<Snapshot> {
+    if !lesson.metadata.compiles && !lesson.has_fixed_variant {
+        return Ok(Snapshot::Skipped);
+    }
+
+    let path = path(lesson);
+    let previous = if path.is_file() {
+        Some(fs::read_to_string(&path).map_err(|e| Error::io(&path, e))?)
+    } else {
+        None
+    };
+    if previous.is_none() && !accept {
+        return Ok(Snapshot::Missing);
+    }
+
+    let build = diagnostics::build(lesson, None, lesson.has_fixed_variant)?;
+    let Some(executable) = build.executable.filter(|_| build.success) else {
+        let errors = build.diagnostics.into_iter().filter(|d| d.is_error());
+        return Ok(Snapshot::Checked(Outcome::BuildFailed(errors.collect())));
+    };
+
+    let run = exec::capture(lesson, &executable, limits)?;
+    if let Some(exceeded) = run.exceeded {
+        return Ok(Snapshot::Checked(Outcome::Exceeded(exceeded)));
+    }
+    if !run.status.success() {
+        return Ok(Snapshot::Checked(Outcome::Crashed {
+            status: run.status,
+            stderr: run.stderr,
+        }));
+    }
+
+    let actual = diff::normalize(&run.stdout);
+    let expected = previous.as_deref().map(diff::normalize);
+    if expected.as_deref() == Some(actual.as_str()) {
+        return Ok(Snapshot::Checked(Outcome::Passed));
+    }
+
+    if accept {
+        let dir = lesson.dir.join(DIR);
+        fs::create_dir_all(&dir).map_err(|e| Error::io(&dir, e))?;
+        fs::write(&path, format!("{}\n", actual)).map_err(|e| Error::io(&path, e))?;
+        return Ok(Snapshot::Saved {
+            previous: expected,
+            current: actual,
+        });
+    }
+
+    Ok(Snapshot::Checked(Outcome::Mismatch {
+        expected: expected.unwrap_or_default(),
+        actual,
+    }))
+}
+
+/// Prints a one-line summary per lesson, followed by what changed.
+pub fn print(lesson: &Lesson, snapshot: &Snapshot, guide: &Guide) {
+    let (status, note) = match snapshot {
+        Snapshot::Skipped => ("skipped", "doesn't compile, nothing to snapshot"),
+        Snapshot::Missing => ("FAILED", "no snapshot yet, save one with --accept"),
+        Snapshot::Saved { previous: None, .. } => ("saved", "new snapshot"),
+        Snapshot::Saved {
+            previous: Some(_), ..
+        } => ("updated", "snapshot updated"),
+        Snapshot::Checked(Outcome::Passed) => ("ok", "output matches"),
+        Snapshot::Checked(_) => ("FAILED", "output changed"),
+    };
+    let variant = if lesson.has_fixed_variant && !matches!(snapshot, Snapshot::Skipped) {
+        "fixed variant, "
+    } else {
+        ""
+    };
+    println!(
+        "{:7} {}  {} ({}{})",
+        status,
+        lesson.id(),
+        lesson.name,
+        variant,
+        note
+    );
+
+    match snapshot {
+        Snapshot::Saved {
+            previous: Some(previous),
+            current,
+        } => {
+            diff::print(&diff::lines(previous, current), "          ");
+        }
+        Snapshot::Checked(Outcome::Mismatch { expected, actual }) => {
+            println!("          - snapshot, + actual; run with --accept if the change is intended");
+            diff::print(&diff::lines(expected, actual), "          ");
+        }
+        Snapshot::Checked(Outcome::BuildFailed(errors)) => guide.print_errors(errors),
+        Snapshot::Checked(Outcome::Exceeded(exceeded)) => {
+            println!("          it {}", exceeded);
+        }
+        Snapshot::Checked(Outcome::Crashed { status, stderr }) => {
+            println!("          it exited with {}", status);
+            print!("{}", stderr);
+        }
+        _ => {}
+    }
+}
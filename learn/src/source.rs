@@ -0,0 +1,99 @@
+//! Splits a lesson's source into what it's made of: the prose of its `/* ... */` blocks,
+//! the `// ===== TOPIC =====` headings between them, and the code in between.
+
+use crate::comments;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Segment {
+    /// A `// ========= LOOPS =========` line, as `Loops`.
+    Heading(String),
+    /// The text of a comment block, one entry per line; empty entries separate paragraphs.
+    Prose(Vec<String>),
+    /// Code, dedented, with `line` being the 1-based line of its first line in the file.
+    Code { line: usize, lines: Vec<String> },
+}
+
+pub fn segments(contents: &str) -> Vec<Segment> {
+    let lines: Vec<&str> = contents.lines().collect();
+    let mut segments = Vec::new();
+    let mut code: Option<(usize, Vec<&str>)> = None;
+
+    let mut index = 0;
+    while index < lines.len() {
+        let line = lines[index];
+        let trimmed = line.trim();
+
+        if trimmed.starts_with("/*") {
+            let start = index;
+            while !lines[index].trim_end().ends_with("*/") && index + 1 < lines.len() {
+                index += 1;
+            }
+            push_code(&mut segments, code.take());
+            segments.push(Segment::Prose(comments::text(&lines[start..=index])));
+        } else if let Some(heading) = heading(trimmed) {
+            push_code(&mut segments, code.take());
+            segments.push(Segment::Heading(heading));
+        } else if !comments::is_tag(line) {
+            code.get_or_insert_with(|| (index + 1, Vec::new()))
+                .1
+                .push(line);
+        }
+
+        index += 1;
+    }
+    push_code(&mut segments, code);
+
+    segments
+}
+
+/// `// ========= IF EXPRESSIONS =========` -> `If expressions`
+pub fn heading(line: &str) -> Option<String> {
+    let text = line.strip_prefix("//")?.trim();
+    let text = text.strip_prefix("===")?.trim_start_matches('=');
+    let text = text.strip_suffix("===")?.trim_end_matches('=').trim();
+    if text.is_empty() {
+        return None;
+    }
+
+    let lower = text.to_lowercase();
+    let mut chars = lower.chars();
+    chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+}
+
+/// Adds a run of code, unless it's nothing but blank lines and braces, like the `}` closing `main`.
+fn push_code(segments: &mut Vec<Segment>, code: Option<(usize, Vec<&str>)>) {
+    let Some((first_line, lines)) = code else {
+        return;
+    };
+
+    let Some(start) = lines.iter().position(|line| !line.trim().is_empty()) else {
+        return;
+    };
+    let end = lines
+        .iter()
+        .rposition(|line| !line.trim().is_empty())
+        .unwrap_or(start);
+    let lines = &lines[start..=end];
+    if lines
+        .iter()
+        .all(|line| matches!(line.trim(), "" | "{" | "}" | "};"))
+    {
+        return;
+    }
+
+    let indent = lines
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    segments.push(Segment::Code {
+        line: first_line + start,
+        lines: lines
+            .iter()
+            .map(|line| line.get(indent..).unwrap_or("").trim_end().to_string())
+            .collect(),
+    });
+}
@@ -0,0 +1,515 @@
+//! A guided walk through a lesson: one comment block at a time, followed by the code after it and
+//! only what that code printed.
+//!
+//! Running each piece of code on its own wouldn't work, since it uses the variables defined
+//! before it. Instead, a scratch copy of the lesson gets a marker printed at the start of every
+//! step, and the output of a single run is split at those markers. Steps in functions other than
+//! `main` print one more marker when the function returns, so what its caller prints next goes
+//! back to the caller's step.
+//!
+//! The same markers make a program of any single step: the whole lesson still runs, but `print!`
+//! and `println!` only print while that step's code is running. The book runs each snippet
+//! this way.
+
+use std::fs;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::path::Path;
+use std::process::Command;
+
+use crate::diagnostics::{self, Diagnostic};
+use crate::error::{Error, Result};
+use crate::exec::{self, Exceeded, Limits};
+use crate::expect;
+use crate::lesson::{Lesson, FIXED_FEATURE};
+use crate::source::{self, Segment};
+
+/// Starts the lines the markers print; no lesson prints it on its own.
+const MARKER: &str = "\u{1e}learn-tour ";
+
+/// Appended to the scratch copy for the markers of functions other than `main`.
+const GUARD: &str = "
+mod learn_tour {
+    pub struct Step;
+
+    impl Step {
+        pub fn enter(step: usize) -> Self {
+            println!(\"\\u{1e}learn-tour enter {}\", step);
+            Step
+        }
+    }
+
+    impl Drop for Step {
+        fn drop(&mut self) {
+            println!(\"\\u{1e}learn-tour leave\");
+        }
+    }
+}
+";
+
+/// Appended to a copy of the lesson that only shows what one step prints; see `focused`.
+const FOCUS: &str = "
+mod learn_tour {
+    use std::cell::RefCell;
+
+    const SHOWN: usize = {shown};
+
+    thread_local! {
+        // The step running now, after the ones of the functions that called it.
+        static STEPS: RefCell<Vec<usize>> = RefCell::new(vec![0]);
+    }
+
+    pub fn step(step: usize) {
+        STEPS.with(|steps| *steps.borrow_mut().last_mut().unwrap() = step);
+    }
+
+    pub fn shown() -> bool {
+        STEPS.with(|steps| steps.borrow().last() == Some(&SHOWN))
+    }
+
+    pub struct Step;
+
+    impl Step {
+        pub fn enter(step: usize) -> Self {
+            STEPS.with(|steps| steps.borrow_mut().push(step));
+            Step
+        }
+    }
+
+    impl Drop for Step {
+        fn drop(&mut self) {
+            STEPS.with(|steps| steps.borrow_mut().pop());
+        }
+    }
+}
+";
+
+/// Put in front of the first line of that copy, so the lesson's own `print!` and `println!` are
+/// these instead of the standard ones.
+const QUIET: &str = "macro_rules! print { ($($arg:tt)*) => { if learn_tour::shown() { \
+    std::print!($($arg)*) } } } macro_rules! println { ($($arg:tt)*) => { \
+    if learn_tour::shown() { std::println!($($arg)*) } } } ";
+
+/// Where a statement can go.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Place {
+    Main,
+    /// Any other function, numbered in the order they appear.
+    Function(usize),
+}
+
+#[derive(Default)]
+pub struct Step {
+    pub heading: Option<String>,
+    pub prose: Vec<String>,
+    /// The code after the comment block, and the 1-based line it starts on.
+    pub code: Option<(usize, Vec<String>)>,
+    /// The first line of the code where a statement can go, which is where its output starts.
+    marker: Option<(usize, Place)>,
+}
+
+impl Step {
+    /// Whether any of the step's code runs, so it prints something of its own.
+    pub fn runs_alone(&self) -> bool {
+        self.marker.is_some()
+    }
+}
+
+/// What each step printed, or why the lesson couldn't be toured.
+pub enum Outputs {
+    Printed {
+        /// One entry per step.
+        steps: Vec<String>,
+        exceeded: Option<Exceeded>,
+    },
+    Failed(Vec<Diagnostic>),
+}
+
+/// Splits the lesson's `src/main.rs` into steps, each one starting at a comment block.
+pub fn steps(contents: &str) -> Vec<Step> {
+    let statements = statement_lines(contents);
+    let mut steps: Vec<Step> = Vec::new();
+    let mut heading = None;
+
+    for segment in source::segments(contents) {
+        match segment {
+            Segment::Heading(text) => heading = Some(text),
+            Segment::Prose(prose) => steps.push(Step {
+                heading: heading.take(),
+                prose,
+                ..Step::default()
+            }),
+            Segment::Code { line, lines } => {
+                if steps.last().is_none_or(|step| step.code.is_some()) {
+                    steps.push(Step {
+                        heading: heading.take(),
+                        ..Step::default()
+                    });
+                }
+                let step = steps.last_mut().unwrap();
+                step.marker = (line..line + lines.len()).find_map(|line| {
+                    let place = statements.get(line - 1).copied().flatten()?;
+                    Some((line, place))
+                });
+                step.code = Some((line, lines));
+            }
+        }
+    }
+
+    steps
+}
+
+/// For each line, whether a statement could be inserted at its start, and in which function:
+/// inside a function body or one of its `if`/`loop` blocks, and right after a complete statement.
+fn statement_lines(contents: &str) -> Vec<Option<Place>> {
+    #[derive(Clone, Copy, PartialEq)]
+    enum Block {
+        Body(Place),
+        Other,
+    }
+
+    let mut functions = 0;
+    let mut stack: Vec<Block> = Vec::new();
+    let mut previous = None;
+    let mut in_comment = false;
+    let mut statements = Vec::new();
+
+    for line in contents.lines() {
+        let code = strip_comments(line, &mut in_comment);
+        let code = code.trim();
+        let starts_statement = matches!(previous, Some(';' | '{' | '}'))
+            && !code.starts_with("else")
+            && !code.starts_with('.');
+        statements.push(match stack.last() {
+            Some(Block::Body(place)) if starts_statement => Some(*place),
+            _ => None,
+        });
+
+        for (index, c) in code.char_indices() {
+            match c {
+                '{' => {
+                    let before = code[..index].trim_end();
+                    // Items, `match` arms and struct literals can't hold statements.
+                    let struct_literal = before
+                        .rsplit(|c: char| !c.is_alphanumeric() && c != '_')
+                        .next()
+                        .is_some_and(|word| word.starts_with(char::is_uppercase));
+                    let kind = match stack.last() {
+                        _ if before.contains("fn main(") => Block::Body(Place::Main),
+                        _ if before.contains("fn ") => {
+                            functions += 1;
+                            Block::Body(Place::Function(functions))
+                        }
+                        Some(Block::Body(place))
+                            if !before.contains("match ") && !struct_literal =>
+                        {
+                            Block::Body(*place)
+                        }
+                        _ => Block::Other,
+                    };
+                    stack.push(kind);
+                }
+                '}' => {
+                    stack.pop();
+                }
+                _ => {}
+            }
+        }
+
+        if !code.is_empty() {
+            previous = code.chars().last();
+        }
+    }
+
+    statements
+}
+
+/// The line without comments and string or character literals, so only real braces are left.
+fn strip_comments(line: &str, in_comment: &mut bool) -> String {
+    let chars: Vec<char> = line.chars().collect();
+    let mut code = String::new();
+    let mut i = 0;
+    while i < chars.len() {
+        if *in_comment {
+            if chars[i] == '*' && chars.get(i + 1) == Some(&'/') {
+                *in_comment = false;
+                i += 1;
+            }
+        } else if chars[i] == '/' && chars.get(i + 1) == Some(&'/') {
+            break;
+        } else if chars[i] == '/' && chars.get(i + 1) == Some(&'*') {
+            *in_comment = true;
+            i += 1;
+        } else if chars[i] == '"' {
+            i += 1;
+            while i < chars.len() && chars[i] != '"' {
+                i += if chars[i] == '\\' { 2 } else { 1 };
+            }
+            code.push_str("\"\"");
+        } else if chars[i] == '\'' && chars.get(i + 2) == Some(&'\'') {
+            i += 2;
+        } else {
+            code.push(chars[i]);
+        }
+        i += 1;
+    }
+    code
+}
+
+/// Builds a scratch copy of the lesson with a marker at every step that runs alone, runs it, and
+/// splits what it printed between the steps.
+pub fn outputs(lesson: &Lesson, steps: &[Step], limits: &Limits) -> Result<Outputs> {
+    let scratch = lesson.scratch_dir("tour");
+    let main = Path::new("src").join("main.rs");
+    for file in expect::sources(&lesson.dir.join("src"))? {
+        let relative = file.strip_prefix(&lesson.dir).unwrap_or(&file);
+        let copy = scratch.join(relative);
+        if let Some(dir) = copy.parent() {
+            fs::create_dir_all(dir).map_err(|e| Error::io(dir, e))?;
+        }
+
+        let mut contents = fs::read_to_string(&file).map_err(|e| Error::io(&file, e))?;
+        if relative == main {
+            contents = instrument(&contents, steps);
+        }
+        fs::write(&copy, contents).map_err(|e| Error::io(&copy, e))?;
+    }
+
+    let rustc = std::env::var_os("RUSTC").unwrap_or_else(|| "rustc".into());
+    let mut command = Command::new(rustc);
+    command
+        .current_dir(&scratch)
+        .arg("--edition")
+        .arg(&lesson.edition)
+        .arg("--error-format=json")
+        .arg("--cap-lints=allow")
+        .arg("-o")
+        .arg("tour");
+    if lesson.has_fixed_variant {
+        command
+            .arg("--cfg")
+            .arg(format!("feature=\"{}\"", FIXED_FEATURE));
+    }
+    command.arg(&main);
+
+    let output = command.output().map_err(|source| Error::Spawn {
+        program: command.get_program().to_string_lossy().into_owned(),
+        source,
+    })?;
+    if !output.status.success() {
+        let errors = diagnostics::parse_rustc(&String::from_utf8_lossy(&output.stderr))
+            .into_iter()
+            .filter(Diagnostic::is_error)
+            .collect();
+        return Ok(Outputs::Failed(errors));
+    }
+
+    let mut program = Command::new(scratch.join("tour"));
+    program.current_dir(&lesson.dir);
+    let run = exec::execute(program, limits, false)?;
+
+    // Whatever prints before the first marker belongs to the first step. The last entry is the
+    // step printing now, and the ones before it belong to the functions that called it.
+    let mut printed = vec![String::new(); steps.len()];
+    let mut current = vec![0];
+    for line in run.stdout.lines() {
+        let Some(marker) = line.strip_prefix(MARKER) else {
+            let step = *current.last().unwrap();
+            printed[step].push_str(line);
+            printed[step].push('\n');
+            continue;
+        };
+        match marker.split_once(' ') {
+            Some(("step", step)) => *current.last_mut().unwrap() = step.parse().unwrap_or(0),
+            Some(("enter", step)) => current.push(step.parse().unwrap_or(0)),
+            _ if current.len() > 1 => {
+                current.pop();
+            }
+            _ => {}
+        }
+    }
+
+    // A panic belongs to the step it happened in.
+    printed[*current.last().unwrap()].push_str(&run.stderr);
+
+    Ok(Outputs::Printed {
+        steps: printed,
+        exceeded: run.exceeded,
+    })
+}
+
+/// Prefixes the first line of every step's code with a statement printing its marker, keeping
+/// the line numbers as they were. The first step in a function other than `main` enters it
+/// with a guard that marks when it returns.
+fn instrument(contents: &str, steps: &[Step]) -> String {
+    let marker = |index| format!("println!(\"{}step {}\");", MARKER.escape_default(), index);
+    mark(contents, steps, marker).join("\n") + "\n" + GUARD
+}
+
+/// The lesson as a program that runs in full, but only prints what step `shown` prints, with the
+/// line numbers as they were.
+pub fn focused(contents: &str, steps: &[Step], shown: usize) -> String {
+    let mut lines = mark(contents, steps, |index| {
+        format!("learn_tour::step({});", index)
+    });
+    if let Some(first) = lines.first_mut() {
+        first.insert_str(0, QUIET);
+    }
+    lines.join("\n") + "\n" + &FOCUS.replace("{shown}", &shown.to_string())
+}
+
+/// The lesson's lines with `marker` in front of the first line of every step that runs alone, or
+/// a guard entering the step for the first step of a function other than `main`.
+fn mark(contents: &str, steps: &[Step], marker: impl Fn(usize) -> String) -> Vec<String> {
+    let mut lines: Vec<String> = contents.lines().map(str::to_string).collect();
+    let mut entered = Vec::new();
+    for (index, step) in steps.iter().enumerate() {
+        let Some((line, place)) = step.marker else {
+            continue;
+        };
+        let marker = match place {
+            Place::Function(_) if !entered.contains(&place) => {
+                entered.push(place);
+                format!("let _learn_tour = learn_tour::Step::enter({});", index)
+            }
+            _ => marker(index),
+        };
+        lines[line - 1] = format!("{} {}", marker, lines[line - 1]);
+    }
+
+    lines
+}
+
+/// Shows the steps one by one, waiting for Enter between them when reading from a terminal.
+pub fn present(lesson: &Lesson, steps: &[Step], printed: &[String], exceeded: Option<&Exceeded>) {
+    let interactive = io::stdin().is_terminal();
+    let mut input = io::stdin().lock();
+
+    for (index, step) in steps.iter().enumerate() {
+        println!(
+            "── {} {}, step {} of {} ──\n",
+            lesson.id(),
+            lesson.metadata.title,
+            index + 1,
+            steps.len()
+        );
+        if let Some(heading) = &step.heading {
+            println!("{}\n", heading.to_uppercase());
+        }
+        for line in &step.prose {
+            println!("{}", line);
+        }
+
+        if let Some((first, code)) = &step.code {
+            if !step.prose.is_empty() {
+                println!();
+            }
+            let width = (first + code.len()).to_string().len();
+            for (offset, line) in code.iter().enumerate() {
+                let numbered = format!("  {:>width$} | {}", first + offset, line);
+                println!("{}", numbered.trim_end());
+            }
+
+            println!();
+            if code
+                .iter()
+                .all(|line| line.is_empty() || line.starts_with("//"))
+            {
+                println!("(commented out; uncomment it to try it)");
+            } else if step.marker.is_none() {
+                println!("(nothing here runs on its own)");
+            } else if printed[index].is_empty() {
+                println!("(prints nothing)");
+            } else {
+                println!("Output:");
+                for line in printed[index].lines() {
+                    println!("  {}", line);
+                }
+            }
+        }
+
+        let last = index + 1 == steps.len();
+        if last {
+            if let Some(exceeded) = exceeded {
+                println!("\nerror: the program {}", exceeded);
+            }
+        } else if interactive {
+            print!("\n[Enter] next step, [q] quit ");
+            let _ = io::stdout().flush();
+            let mut answer = String::new();
+            if input.read_line(&mut answer).unwrap_or(0) == 0 || answer.trim() == "q" {
+                return;
+            }
+            println!();
+        } else {
+            println!();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LESSON: &str = "/*
+ * Main starts.
+ */
+fn main() {
+    println!(\"main\");
+    /*
+     * Main calls inner.
+     */
+    inner();
+    println!(\"back in main\");
+}
+
+fn inner() {
+    /*
+     * Inner prints.
+     */
+    println!(\"inner\");
+}
+";
+
+    /// Compiles and runs the lesson with only `step` shown.
+    fn run_focused(step: usize) -> String {
+        let dir =
+            std::env::temp_dir().join(format!("learn-focused-{}-{}", std::process::id(), step));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("main.rs"), focused(LESSON, &steps(LESSON), step)).unwrap();
+        let rustc = std::env::var_os("RUSTC").unwrap_or_else(|| "rustc".into());
+        let status = Command::new(rustc)
+            .current_dir(&dir)
+            .args([
+                "--edition",
+                "2021",
+                "--cap-lints=allow",
+                "-o",
+                "focused",
+                "main.rs",
+            ])
+            .status()
+            .unwrap();
+        assert!(status.success());
+        let output = Command::new(dir.join("focused")).output().unwrap();
+        let _ = fs::remove_dir_all(&dir);
+        String::from_utf8(output.stdout).unwrap()
+    }
+
+    #[test]
+    fn a_focused_lesson_only_prints_its_step() {
+        assert_eq!(run_focused(0), "main\n");
+        assert_eq!(run_focused(1), "back in main\n");
+        assert_eq!(run_focused(2), "inner\n");
+    }
+
+    #[test]
+    fn focusing_keeps_the_line_numbers() {
+        let steps = steps(LESSON);
+        assert!(steps.iter().all(Step::runs_alone));
+        let focused = focused(LESSON, &steps, 1);
+        assert_eq!(
+            focused.lines().nth(9).unwrap().trim(),
+            "println!(\"back in main\");"
+        );
+    }
+}
@@ -0,0 +1,246 @@
+//! Commented-out snippets that show off a compiler error once they're uncommented.
+//!
+//! A snippet is tagged with a comment right above it, naming it and, optionally, the error code
+//! (or lint) rustc should report:
+//!
+//! ```text
+//! // @try-error immutable-assign E0384
+//! // x = x + 1;
+//! ```
+//!
+//! Every `//` line right below the tag belongs to the snippet. `learn try` uncomments it in a
+//! scratch copy of the lesson, compiles that, and shows rustc's errors next to the lesson's
+//! explanation: the comment block right above the tag or, if there's none, right below the snippet.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::diagnostics::{self, Diagnostic};
+use crate::error::{Error, Result};
+use crate::explain::Guide;
+use crate::lesson::{Lesson, FIXED_FEATURE};
+use crate::{comments, expect};
+
+const TAG: &str = "@try-error";
+
+pub struct Snippet {
+    pub name: String,
+    /// The error code or lint rustc should report, e.g. `E0384` or `unconditional_panic`.
+    pub code: Option<String>,
+    /// Relative to the lesson's directory, e.g. `src/main.rs`.
+    pub file: PathBuf,
+    /// The 1-based line of the snippet's first line of code.
+    pub line: usize,
+    /// The snippet's code, uncommented.
+    pub code_lines: Vec<String>,
+    pub explanation: Vec<String>,
+}
+
+/// Collects every tagged snippet in the lesson's `src` directory, in the order they appear.
+pub fn snippets(lesson: &Lesson) -> Result<Vec<Snippet>> {
+    let mut snippets = Vec::new();
+    for file in expect::sources(&lesson.dir.join("src"))? {
+        let contents = fs::read_to_string(&file).map_err(|e| Error::io(&file, e))?;
+        let relative = file.strip_prefix(&lesson.dir).unwrap_or(&file);
+        snippets.extend(parse(relative, &contents));
+    }
+
+    Ok(snippets)
+}
+
+pub fn find<'a>(lesson: &Lesson, snippets: &'a [Snippet], name: &str) -> Result<&'a Snippet> {
+    snippets
+        .iter()
+        .find(|snippet| snippet.name == name)
+        .ok_or_else(|| Error::SnippetNotFound {
+            lesson: lesson.id(),
+            name: name.to_string(),
+        })
+}
+
+fn parse(file: &Path, contents: &str) -> Vec<Snippet> {
+    let lines: Vec<&str> = contents.lines().collect();
+    let mut snippets = Vec::new();
+
+    for (index, line) in lines.iter().enumerate() {
+        let Some(rest) = comments::tag(line, TAG) else {
+            continue;
+        };
+        let mut words = rest.split_whitespace();
+        let Some(name) = words.next() else {
+            continue;
+        };
+
+        let end = (index + 1..lines.len())
+            .find(|&i| uncomment(lines[i]).is_none())
+            .unwrap_or(lines.len());
+        let code_lines: Vec<String> = lines[index + 1..end]
+            .iter()
+            .filter_map(|line| uncomment(line))
+            .collect();
+
+        let mut explanation = comments::block_above(&lines, index);
+        if explanation.is_empty() {
+            explanation = comments::first_paragraph(comments::block_below(&lines, end));
+        }
+
+        snippets.push(Snippet {
+            name: name.to_string(),
+            code: words.next().map(str::to_string),
+            file: file.to_path_buf(),
+            line: index + 2,
+            code_lines,
+            explanation,
+        });
+    }
+
+    snippets
+}
+
+/// `    // x = x + 1;` -> `    x = x + 1;`. Returns `None` for anything but a plain `//` comment.
+fn uncomment(line: &str) -> Option<String> {
+    let trimmed = line.trim_start();
+    if !trimmed.starts_with("//") || comments::is_tag(line) {
+        return None;
+    }
+    let indent = &line[..line.len() - trimmed.len()];
+    let code = trimmed[2..].strip_prefix(' ').unwrap_or(&trimmed[2..]);
+    Some(format!("{}{}", indent, code))
+}
+
+/// Compiles a scratch copy of the lesson with the snippet uncommented and returns rustc's errors.
+pub fn compile(lesson: &Lesson, snippet: &Snippet) -> Result<Vec<Diagnostic>> {
+    let scratch = lesson.scratch_dir(&format!("try-{}", snippet.name));
+    let src = lesson.dir.join("src");
+    for file in expect::sources(&src)? {
+        let relative = file.strip_prefix(&lesson.dir).unwrap_or(&file);
+        let copy = scratch.join(relative);
+        if let Some(dir) = copy.parent() {
+            fs::create_dir_all(dir).map_err(|e| Error::io(dir, e))?;
+        }
+
+        let mut contents = fs::read_to_string(&file).map_err(|e| Error::io(&file, e))?;
+        if relative == snippet.file {
+            contents = enable(&contents, snippet);
+        }
+        fs::write(&copy, contents).map_err(|e| Error::io(&copy, e))?;
+    }
+
+    let rustc = std::env::var_os("RUSTC").unwrap_or_else(|| "rustc".into());
+    let mut command = Command::new(rustc);
+    command
+        .current_dir(&scratch)
+        .arg("--edition")
+        .arg(&lesson.edition)
+        .arg("--error-format=json")
+        // Lints like `unconditional_panic` only fire once MIR is built, which metadata skips.
+        .arg("--emit=mir")
+        .arg("-o")
+        .arg("snippet.mir");
+    if lesson.has_fixed_variant {
+        // Keep the lesson's own intentional errors out of the way.
+        command
+            .arg("--cfg")
+            .arg(format!("feature=\"{}\"", FIXED_FEATURE));
+    }
+    command.arg(Path::new("src").join("main.rs"));
+
+    let output = command.output().map_err(|source| Error::Spawn {
+        program: command.get_program().to_string_lossy().into_owned(),
+        source,
+    })?;
+
+    Ok(
+        diagnostics::parse_rustc(&String::from_utf8_lossy(&output.stderr))
+            .into_iter()
+            .filter(|diagnostic| diagnostic.is_error() && !diagnostic.spans.is_empty())
+            .collect(),
+    )
+}
+
+/// Uncomments the snippet's lines, leaving everything else, line numbers included, as it was.
+fn enable(contents: &str, snippet: &Snippet) -> String {
+    let first = snippet.line - 1;
+    let last = first + snippet.code_lines.len();
+    let mut lines: Vec<String> = contents.lines().map(str::to_string).collect();
+    for (line, code) in lines[first..last].iter_mut().zip(&snippet.code_lines) {
+        *line = code.clone();
+    }
+
+    lines.join("\n") + "\n"
+}
+
+/// Whether rustc reported what the snippet's tag promises, or any error at all if it promises nothing.
+pub fn matches(snippet: &Snippet, errors: &[Diagnostic]) -> bool {
+    match &snippet.code {
+        Some(code) => errors
+            .iter()
+            .any(|error| error.code() == Some(code.as_str())),
+        None => !errors.is_empty(),
+    }
+}
+
+/// Prints the snippets a lesson has, for `learn try <lesson>` without a snippet name.
+pub fn print_list(lesson: &Lesson, snippets: &[Snippet]) {
+    println!(
+        "{}  {} has {} snippet{} to try:",
+        lesson.id(),
+        lesson.name,
+        snippets.len(),
+        if snippets.len() == 1 { "" } else { "s" }
+    );
+    let width = snippets.iter().map(|s| s.name.len()).max().unwrap_or(0);
+    for snippet in snippets {
+        println!(
+            "  {:width$}  {}:{}  {}",
+            snippet.name,
+            snippet.file.display(),
+            snippet.line,
+            snippet.code.as_deref().unwrap_or("")
+        );
+    }
+}
+
+/// Prints the lesson's explanation, the snippet, and what rustc made of it.
+pub fn print(lesson: &Lesson, snippet: &Snippet, errors: &[Diagnostic], guide: &Guide) {
+    println!(
+        "{} {}, {} ({}:{})\n",
+        lesson.id(),
+        lesson.name,
+        snippet.name,
+        snippet.file.display(),
+        snippet.line
+    );
+    for line in &snippet.explanation {
+        println!("{}", format!("  {}", line).trim_end());
+    }
+    println!();
+    for line in &snippet.code_lines {
+        println!("  | {}", line);
+    }
+    println!();
+
+    if errors.is_empty() {
+        println!("It compiled without errors, so the lesson's explanation may be out of date.");
+        return;
+    }
+
+    println!("Uncommented, rustc says:\n");
+    guide.print_errors(errors);
+
+    if let Some(code) = &snippet.code {
+        if !matches(snippet, errors) {
+            let reported: Vec<&str> = errors.iter().filter_map(Diagnostic::code).collect();
+            println!(
+                "\nnote: the lesson expects {}, but rustc reported {}",
+                code,
+                if reported.is_empty() {
+                    "errors without a code".to_string()
+                } else {
+                    reported.join(", ")
+                }
+            );
+        }
+    }
+}
@@ -0,0 +1,89 @@
+//! Re-runs a lesson, a challenge or its checks every time one of the lesson's files is saved.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use crate::error::Result;
+use crate::lesson::Lesson;
+
+/// How often the watched directories are scanned for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(300);
+/// Editors often write a file in several steps, so give them a moment to finish.
+const SETTLE_DELAY: Duration = Duration::from_millis(100);
+
+/// The directories of a lesson that are watched for changes.
+const WATCHED: [&str; 3] = ["src", "examples", "checks"];
+
+/// Last-modified times of every file in the watched directories.
+type Snapshot = BTreeMap<PathBuf, SystemTime>;
+
+/// Calls `action` once, and then again after every change, until the process is stopped.
+/// `action` returns whether the run passed, which is shown in a banner below its output. An error
+/// counts as a failure, and the watch goes on, since the next save may well fix it.
+pub fn watch<F>(lesson: &Lesson, label: &str, mut action: F) -> Result<()>
+where
+    F: FnMut() -> Result<bool>,
+{
+    let dirs: Vec<PathBuf> = WATCHED.iter().map(|dir| lesson.dir.join(dir)).collect();
+    let mut last = snapshot(&dirs);
+
+    loop {
+        // Clear the terminal, so only the output of the latest run is visible.
+        print!("\x1b[2J\x1b[H");
+        let passed = action().unwrap_or_else(|err| {
+            eprintln!("error: {}", err);
+            false
+        });
+        banner(label, passed);
+
+        loop {
+            thread::sleep(POLL_INTERVAL);
+            let current = snapshot(&dirs);
+            if current != last {
+                thread::sleep(SETTLE_DELAY);
+                last = snapshot(&dirs);
+                break;
+            }
+        }
+    }
+}
+
+fn banner(label: &str, passed: bool) {
+    let (color, status) = if passed {
+        ("\x1b[42;30m", " PASS ")
+    } else {
+        ("\x1b[41;37m", " FAIL ")
+    };
+    println!();
+    println!(
+        "{}{}\x1b[0m {}  (watching src/, examples/ and checks/, Ctrl+C to stop)",
+        color, status, label
+    );
+}
+
+fn snapshot(dirs: &[PathBuf]) -> Snapshot {
+    let mut files = Snapshot::new();
+    for dir in dirs {
+        collect(dir, &mut files);
+    }
+    files
+}
+
+fn collect(dir: &Path, files: &mut Snapshot) {
+    // A directory that doesn't exist (yet) just has nothing to watch.
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect(&path, files);
+        } else if let Ok(modified) = entry.metadata().and_then(|meta| meta.modified()) {
+            files.insert(path, modified);
+        }
+    }
+}
@@ -0,0 +1,68 @@
+// Runs and checks lessons and challenges on the host `learn serve` runs on, streaming what they
+// print into the page. Served with every page; nothing else is loaded from anywhere.
+
+for (const panel of document.querySelectorAll(".console")) {
+    const editor = panel.querySelector("textarea");
+    const output = panel.querySelector(".output");
+    const buttons = panel.querySelectorAll("button");
+    const original = editor ? editor.value : "";
+
+    if (editor) {
+        // Tab indents instead of leaving the editor.
+        editor.addEventListener("keydown", (event) => {
+            if (event.key !== "Tab") {
+                return;
+            }
+            event.preventDefault();
+            editor.setRangeText("    ", editor.selectionStart, editor.selectionEnd, "end");
+        });
+    }
+
+    for (const button of buttons) {
+        button.addEventListener("click", async () => {
+            const action = button.dataset.action;
+            if (action === "reset") {
+                editor.value = original;
+                return;
+            }
+
+            const query = new URLSearchParams({ lesson: panel.dataset.lesson });
+            if (panel.dataset.challenge) {
+                query.set("challenge", panel.dataset.challenge);
+            }
+            if (panel.dataset.step) {
+                query.set("step", panel.dataset.step);
+            }
+            if (action === "fixed") {
+                query.set("fixed", "true");
+            }
+            const path = action === "check" ? "/check" : "/run";
+
+            buttons.forEach((button) => (button.disabled = true));
+            output.hidden = false;
+            output.textContent = "";
+            try {
+                const response = await fetch(`${path}?${query}`, {
+                    method: "POST",
+                    // The server only runs code for requests carrying this header.
+                    headers: { "X-Learn-Playground": "1" },
+                    body: editor ? editor.value : "",
+                });
+                const reader = response.body.getReader();
+                const decoder = new TextDecoder();
+                for (;;) {
+                    const { done, value } = await reader.read();
+                    if (done) {
+                        break;
+                    }
+                    output.textContent += decoder.decode(value, { stream: true });
+                    output.scrollTop = output.scrollHeight;
+                }
+            } catch (error) {
+                output.textContent += `\nerror: lost the connection to learn serve (${error.message})`;
+            } finally {
+                buttons.forEach((button) => (button.disabled = false));
+            }
+        });
+    }
+}
@@ -0,0 +1,125 @@
+// A tiny property-based testing engine, compiled into the hidden test harness of a challenge.
+//
+// for_all feeds a property thousands of random inputs. When one of them fails, it looks for
+// the smallest failing input, so the report says "fails for year 1700" instead of some
+// arbitrary year that happens to share the same bug.
+
+use std::fmt::Debug;
+use std::ops::RangeInclusive;
+use std::panic::{self, AssertUnwindSafe};
+
+/// How many random inputs every property is checked against.
+pub const CASES: usize = 5000;
+
+/// xorshift64*: small, fast and more than random enough for picking test inputs.
+pub struct Rng(u64);
+
+impl Rng {
+    /// Seeded from $LEARN_SEED when it's set, so a failure can be reproduced.
+    pub fn from_env() -> Self {
+        let seed = std::env::var("LEARN_SEED")
+            .ok()
+            .and_then(|seed| seed.parse().ok())
+            .unwrap_or_else(|| {
+                std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .map(|time| time.as_nanos() as u64)
+                    .unwrap_or(0x2545_F491_4F6C_DD1D)
+            });
+        Rng(seed.max(1))
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.0
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 >> 12;
+        self.0 ^= self.0 << 25;
+        self.0 ^= self.0 >> 27;
+        self.0.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    pub fn in_range(&mut self, range: &RangeInclusive<i64>) -> i64 {
+        let span = (range.end() - range.start()) as u64 + 1;
+        range.start() + (self.next_u64() % span) as i64
+    }
+}
+
+/// Runs the property, turning a panic in the learner's code into a failure.
+fn run<F>(property: &F, input: i64) -> Result<(), String>
+where
+    F: Fn(i64) -> Result<(), String>,
+{
+    match panic::catch_unwind(AssertUnwindSafe(|| property(input))) {
+        Ok(result) => result,
+        Err(payload) => {
+            let message = payload
+                .downcast_ref::<String>()
+                .cloned()
+                .or_else(|| payload.downcast_ref::<&str>().map(|s| s.to_string()))
+                .unwrap_or_else(|| "unknown panic".to_string());
+            Err(format!("it panicked: {}", message))
+        }
+    }
+}
+
+/// Checks `property` against random inputs from `range`, plus both of its ends.
+/// Panics with the smallest failing input, described as `<label> <input>`.
+pub fn for_all<F>(label: &str, range: RangeInclusive<i64>, property: F)
+where
+    F: Fn(i64) -> Result<(), String>,
+{
+    let mut rng = Rng::from_env();
+    let seed = rng.seed();
+
+    let mut inputs = vec![*range.start(), *range.end()];
+    inputs.extend((0..CASES).map(|_| rng.in_range(&range)));
+
+    // The learner's code may panic for thousands of inputs; only the final report should be printed.
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+
+    let failure = inputs
+        .into_iter()
+        .find(|&input| run(&property, input).is_err())
+        .map(|failing| {
+            // Every range here is small, so the smallest failing input is found by walking up to it.
+            let smallest = (*range.start()..=failing)
+                .find(|&input| run(&property, input).is_err())
+                .unwrap_or(failing);
+            (smallest, run(&property, smallest).unwrap_err())
+        });
+
+    panic::set_hook(default_hook);
+
+    let Some((smallest, reason)) = failure else {
+        return;
+    };
+    panic!(
+        "fails for {} {}: {} (LEARN_SEED={})",
+        label, smallest, reason, seed
+    );
+}
+
+/// The usual comparison for a property: the learner's answer must match the reference.
+pub fn same<T: PartialEq + Debug>(expected: T, actual: T) -> Result<(), String> {
+    if expected == actual {
+        Ok(())
+    } else {
+        Err(format!("expected {:?}, got {:?}", expected, actual))
+    }
+}
+
+/// `same`, for a learner's answer that may be another integer type than the reference's. An
+/// answer that doesn't fit in the reference's type can't be the same number, so it's a mismatch.
+pub fn same_number<T, U>(expected: T, actual: U) -> Result<(), String>
+where
+    T: PartialEq + Debug,
+    U: TryInto<T> + Copy + Debug,
+{
+    match actual.try_into() {
+        Ok(converted) => same(expected, converted),
+        Err(_) => Err(format!("expected {:?}, got {:?}", expected, actual)),
+    }
+}
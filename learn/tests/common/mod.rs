@@ -0,0 +1,37 @@
+//! What the tests that run `learn check` on a changed copy of the lessons share.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+#[path = "../../src/copy.rs"]
+mod copy;
+
+pub fn repo() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("..")
+}
+
+/// A fresh copy of the lessons to change, without what they've built.
+pub fn copy(name: &str) -> PathBuf {
+    let root = Path::new(env!("CARGO_TARGET_TMPDIR")).join(name);
+    let _ = fs::remove_dir_all(&root);
+    copy::copy_dir(&repo().join("lessons"), &root.join("lessons")).unwrap();
+    root
+}
+
+/// `learn check 04 <challenge>` in the copy at `root`.
+pub fn check(root: &Path, challenge: &str) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_learn"))
+        .args(["check", "04", challenge])
+        .current_dir(root)
+        .output()
+        .expect("failed to run learn")
+}
+
+pub fn describe(output: &Output) -> String {
+    format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    )
+}
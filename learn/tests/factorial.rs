@@ -0,0 +1,84 @@
+//! Checks the factorial challenge's reference solutions against each other: the `loop`, `while`
+//! and `for` versions the challenge asks for, the checked one and the arbitrary-precision one.
+
+#[allow(dead_code)]
+#[path = "../../lessons/04-control-flow/checks/factorial/reference.rs"]
+mod reference;
+
+use reference::*;
+
+#[test]
+fn loop_while_and_for_agree() {
+    // 20! is the largest factorial that fits in a u64.
+    for number in 0..=20 {
+        let expected = calculate_factorial_for(number);
+        assert_eq!(
+            calculate_factorial_loop(number),
+            expected,
+            "loop, {}!",
+            number
+        );
+        assert_eq!(
+            calculate_factorial_while(number),
+            expected,
+            "while, {}!",
+            number
+        );
+        assert_eq!(calculate_factorial(number), expected, "{}!", number);
+    }
+}
+
+#[test]
+fn checked_agrees_with_the_loops() {
+    for number in 0..=20 {
+        assert_eq!(
+            calculate_factorial_checked(number),
+            Some(calculate_factorial_for(number) as u128),
+            "{}!",
+            number
+        );
+    }
+}
+
+#[test]
+fn checked_gives_up_once_it_no_longer_fits() {
+    assert!(calculate_factorial_checked(34).is_some());
+    assert_eq!(calculate_factorial_checked(35), None);
+    assert_eq!(calculate_factorial_checked(100), None);
+}
+
+#[test]
+fn big_agrees_with_checked() {
+    for number in 0..=34 {
+        assert_eq!(
+            calculate_factorial_big(number).to_string(),
+            calculate_factorial_checked(number).unwrap().to_string(),
+            "{}!",
+            number
+        );
+    }
+}
+
+#[test]
+fn big_goes_past_u128() {
+    assert_eq!(
+        calculate_factorial_big(35).to_string(),
+        "10333147966386144929666651337523200000000"
+    );
+
+    let hundred = calculate_factorial_big(100).to_string();
+    assert_eq!(hundred.len(), 158);
+    assert!(hundred.starts_with("93326215443944152681"), "{}", hundred);
+    assert!(hundred.ends_with(&"0".repeat(24)), "{}", hundred);
+}
+
+#[test]
+fn big_multiplies_by_factors_past_u32() {
+    let mut number = BigUint::one();
+    number.multiply(u64::MAX);
+    number.multiply(u64::MAX);
+    assert_eq!(
+        number.to_string(),
+        (u64::MAX as u128 * u64::MAX as u128).to_string()
+    );
+}
@@ -0,0 +1,18 @@
+//! Fails `cargo test` whenever a lesson uses a glossary term before the lesson that defines it.
+
+use std::process::Command;
+
+#[test]
+fn terms_are_defined_before_they_are_used() {
+    let output = Command::new(env!("CARGO_BIN_EXE_learn"))
+        .args(["glossary", "--check"])
+        .output()
+        .expect("failed to run learn");
+
+    assert!(
+        output.status.success(),
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
@@ -0,0 +1,125 @@
+//! Checks the leap-year toolkit in lesson 04's reference solution: the calendars, the switchover
+//! and the day counts, against dates from history.
+
+// The lessons teach `%`, so the reference sticks to it.
+#[allow(dead_code, clippy::manual_is_multiple_of)]
+#[path = "../../lessons/04-control-flow/checks/leap-year/reference.rs"]
+mod reference;
+
+use reference::*;
+
+const RUSSIA: Switchover = Switchover {
+    year: 1918,
+    month: 2,
+};
+
+#[test]
+fn proleptic_gregorian_is_the_challenge() {
+    for year in 1..=3000 {
+        assert_eq!(
+            is_leap(year as i32, Calendar::ProlepticGregorian),
+            check_is_leap_year(year),
+            "{}",
+            year
+        );
+    }
+}
+
+#[test]
+fn julian_has_no_exceptions_for_centuries() {
+    for year in [1500, 1700, 1900, 2000, 4, 0, -4] {
+        assert!(is_leap(year, Calendar::Julian), "{}", year);
+    }
+    for year in [1901, 2023, -1] {
+        assert!(!is_leap(year, Calendar::Julian), "{}", year);
+    }
+}
+
+#[test]
+fn gregorian_switches_rules_at_the_switchover() {
+    let rome = Calendar::Gregorian(Switchover::ROME);
+    assert!(is_leap(1500, rome));
+    assert!(is_leap(1600, rome));
+    assert!(!is_leap(1700, rome));
+
+    let britain = Calendar::Gregorian(Switchover::BRITAIN);
+    assert!(is_leap(1700, britain));
+    assert!(is_leap(1752, britain));
+    assert!(!is_leap(1800, britain));
+}
+
+#[test]
+fn leap_years_in_a_range() {
+    let years = leap_years(1890..2030, Calendar::ProlepticGregorian);
+    assert_eq!(years.len(), 34);
+    assert_eq!(years.first(), Some(&1892));
+    assert_eq!(years.last(), Some(&2028));
+    assert!(!years.contains(&1900));
+    assert!(years.contains(&2000));
+
+    assert_eq!(
+        leap_years(1896..=1904, Calendar::Julian),
+        [1896, 1900, 1904]
+    );
+}
+
+#[test]
+fn days_in_a_year() {
+    assert_eq!(days_in_year(2023, Calendar::ProlepticGregorian), 365);
+    assert_eq!(days_in_year(2024, Calendar::ProlepticGregorian), 366);
+    assert_eq!(days_in_year(1582, Calendar::ProlepticGregorian), 365);
+    assert_eq!(
+        days_in_year(1582, Calendar::Gregorian(Switchover::ROME)),
+        355
+    );
+    assert_eq!(
+        days_in_year(1752, Calendar::Gregorian(Switchover::BRITAIN)),
+        355
+    );
+    assert_eq!(days_in_year(1918, Calendar::Gregorian(RUSSIA)), 352);
+}
+
+#[test]
+fn days_in_a_month() {
+    assert_eq!(days_in_month(2024, 2, Calendar::ProlepticGregorian), 29);
+    assert_eq!(days_in_month(1900, 2, Calendar::ProlepticGregorian), 28);
+    assert_eq!(days_in_month(1900, 2, Calendar::Julian), 29);
+    assert_eq!(days_in_month(2023, 4, Calendar::Julian), 30);
+    assert_eq!(days_in_month(2023, 12, Calendar::Julian), 31);
+
+    // October 4th 1582 was followed by the 15th, September 2nd 1752 by the 14th in Britain, and
+    // January 31st 1918 by February 14th in Russia.
+    assert_eq!(
+        days_in_month(1582, 10, Calendar::Gregorian(Switchover::ROME)),
+        21
+    );
+    assert_eq!(
+        days_in_month(1752, 9, Calendar::Gregorian(Switchover::BRITAIN)),
+        19
+    );
+    assert_eq!(days_in_month(1918, 2, Calendar::Gregorian(RUSSIA)), 15);
+}
+
+#[test]
+fn switchovers_are_a_year_and_a_month() {
+    assert_eq!("1752-9".parse(), Ok(Switchover::BRITAIN));
+    assert_eq!("1582".parse(), Ok(Switchover::ROME));
+    assert!("1752-13".parse::<Switchover>().is_err());
+    assert!("MDCCLII".parse::<Switchover>().is_err());
+    // By February 5000 the calendars are 35 days apart.
+    assert!("5000-2".parse::<Switchover>().is_err());
+
+    assert_eq!(Switchover::ROME.dropped_days(), 10);
+    assert_eq!(Switchover::BRITAIN.dropped_days(), 11);
+    assert_eq!(RUSSIA.dropped_days(), 13);
+}
+
+#[test]
+fn a_late_switchover_drops_the_whole_month() {
+    let late = Switchover {
+        year: 5000,
+        month: 2,
+    };
+    assert_eq!(days_in_month(5000, 2, Calendar::Gregorian(late)), 0);
+    assert_eq!(days_in_month(5000, 3, Calendar::Gregorian(late)), 31);
+}
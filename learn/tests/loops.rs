@@ -0,0 +1,61 @@
+//! Checks the factorial challenge's reference solution with `learn check`, loops stage included,
+//! and that a solution missing one of the loops is held back by it.
+
+mod common;
+
+use std::fs;
+
+use common::{check, copy, describe, repo};
+
+const FACTORIAL: &str = "lessons/04-control-flow/examples/factorial.rs";
+const REFERENCE: &str = "lessons/04-control-flow/checks/factorial/reference.rs";
+
+#[test]
+fn the_reference_passes_with_a_function_per_loop() {
+    let root = copy("loops-reference");
+    fs::copy(repo().join(REFERENCE), root.join(FACTORIAL)).unwrap();
+
+    let output = check(&root, "factorial");
+    assert!(output.status.success(), "{}", describe(&output));
+    assert!(
+        String::from_utf8_lossy(&output.stdout)
+            .contains("PASS 04 factorial (output, tests, properties, loops)"),
+        "{}",
+        describe(&output)
+    );
+}
+
+#[test]
+fn a_missing_loop_is_reported() {
+    let root = copy("loops-missing");
+    let reference = fs::read_to_string(repo().join(REFERENCE)).unwrap();
+    // Without its `loop`, the `loop` version is left with only a `while`, like another one.
+    let solution = reference.replace(
+        "    loop {
+        if n > number {
+            break factorial;
+        }
+        factorial *= n;
+        n += 1;
+    }",
+        "    while n <= number {
+        factorial *= n;
+        n += 1;
+    }
+    factorial",
+    );
+    assert_ne!(
+        solution, reference,
+        "the reference's `loop` version changed"
+    );
+    fs::write(root.join(FACTORIAL), solution).unwrap();
+
+    let output = check(&root, "factorial");
+    assert!(!output.status.success(), "{}", describe(&output));
+    assert!(
+        String::from_utf8_lossy(&output.stdout)
+            .contains("no `calculate_factorial` function of its own uses `loop` yet"),
+        "{}",
+        describe(&output)
+    );
+}
@@ -0,0 +1,109 @@
+//! Checks a solution to the grader challenge on every grading scheme it ships with, against the
+//! reference solution, and that schemes whose bands don't add up are refused.
+
+mod common;
+
+use std::fs;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use common::{check, copy, describe, repo};
+
+const GRADER: &str = "lessons/04-control-flow/examples/grader.rs";
+const CHECKS: &str = "lessons/04-control-flow/checks/grader";
+
+#[test]
+fn a_solution_passes_on_every_scheme() {
+    let schemes = fs::read_to_string(repo().join(CHECKS).join("schemes.toml")).unwrap();
+    let solutions = solutions(&schemes);
+    assert!(
+        solutions.len() >= 4,
+        "expected the built-in schemes, got {:?}",
+        solutions.iter().map(|(name, _)| name).collect::<Vec<_>>()
+    );
+
+    for (name, solution) in solutions {
+        let root = copy(&format!("scheme-{}", name));
+        fs::write(root.join(GRADER), solution).unwrap();
+        select(&root, &name);
+
+        let output = check(&root, "grader");
+        assert!(output.status.success(), "{}", describe(&output));
+    }
+}
+
+#[test]
+fn overlapping_bands_are_refused() {
+    let root = copy("scheme-overlap");
+    let schemes = root.join(CHECKS).join("schemes.toml");
+    let contents = fs::read_to_string(&schemes).unwrap().replace(
+        "{ from = 50, to = 100, grade = \"pass\" }",
+        "{ from = 45, to = 100, grade = \"pass\" }",
+    );
+    fs::write(&schemes, contents).unwrap();
+
+    let output = check(&root, "grader");
+    assert!(!output.status.success(), "{}", describe(&output));
+    assert!(
+        String::from_utf8_lossy(&output.stderr)
+            .contains("scheme `pass-fail`: bands 0-49 (fail) and 45-100 (pass) overlap"),
+        "{}",
+        describe(&output)
+    );
+}
+
+#[derive(Deserialize)]
+struct Schemes {
+    schemes: Vec<Scheme>,
+}
+
+#[derive(Deserialize)]
+struct Scheme {
+    name: String,
+    bands: Vec<Band>,
+}
+
+#[derive(Deserialize)]
+struct Band {
+    from: u32,
+    to: u32,
+    grade: String,
+}
+
+/// A solution for every scheme, as a `match` on its bands.
+fn solutions(schemes: &str) -> Vec<(String, String)> {
+    let schemes: Schemes = toml::from_str(schemes).unwrap();
+    schemes
+        .schemes
+        .into_iter()
+        .map(|scheme| {
+            let mut solution = String::from(
+                "fn main() {
+    let points = 96;
+    println!(\"You've scored {} points. Your final grade is: {}\", points, calculate_grade(points));
+}
+
+fn calculate_grade(points: u32) -> &'static str {
+    match points {
+",
+            );
+            for band in &scheme.bands {
+                solution.push_str(&format!(
+                    "        {}..={} => {:?},\n",
+                    band.from, band.to, band.grade
+                ));
+            }
+            solution.push_str("        _ => unreachable!(),\n    }\n}\n");
+            (scheme.name, solution)
+        })
+        .collect()
+}
+
+/// Points the copy's `schemes.toml` at `scheme`.
+fn select(root: &Path, scheme: &str) {
+    let path = root.join(CHECKS).join("schemes.toml");
+    let mut schemes: toml::Table = toml::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+    schemes.insert("use".to_string(), scheme.into());
+    fs::write(&path, toml::to_string(&schemes).unwrap()).unwrap();
+}
@@ -0,0 +1,19 @@
+//! Fails `cargo test` whenever a lesson's output drifts from its snapshot.
+//! Run `cargo learn snapshot --accept` to update the snapshots after an intended change.
+
+use std::process::Command;
+
+#[test]
+fn lessons_match_their_snapshots() {
+    let output = Command::new(env!("CARGO_BIN_EXE_learn"))
+        .arg("snapshot")
+        .output()
+        .expect("failed to run learn");
+
+    assert!(
+        output.status.success(),
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
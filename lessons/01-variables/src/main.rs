@@ -1,4 +1,5 @@
 fn main() {
+    // @explains E0384 immutable variables
     /*
      * We define variables using the let keyword.
      * By default, all variables in Rust are immutable.
@@ -9,11 +10,12 @@ fn main() {
      * The following command will not work, and Rust's compiler will complain.
      * Uncomment it to see the error.
      */
+    // @try-error immutable-assign E0384
     // x = x + 1;
 
     /*
      * We can print out values using the println method.
-     * Using variables in the output is done by placing {}, and then providing the variables as arguments.
+     * Using variables in the output is done by placing {}, and then listing the variables after the text.
      * We can use as many variables as we want.
      * The ! after println indicates that we're calling a macro, so println is actually a macro, not a function.
      * Macros don't always follow the same rules as functions.
@@ -131,6 +131,7 @@ fn main() {
      * the twentieth element of an array that only has 5 elements.
      * Uncomment the following line to see the error.
      */
+    // @try-error index-out-of-bounds unconditional_panic
     // println!("The twentieth element in nums is: {}", nums[19]);
 
     /*
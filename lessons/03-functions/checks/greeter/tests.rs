@@ -0,0 +1,20 @@
+// Hidden tests for the greeter challenge. `cargo learn check` compiles them together with
+// examples/greeter.rs, so they can call greet directly.
+//
+// greet only prints, so what it prints is covered by expected.txt. These make sure it
+// accepts any name as a string slice.
+
+#[test]
+fn greets_the_name_from_main() {
+    greet("Lazar");
+}
+
+#[test]
+fn greets_an_empty_name() {
+    greet("");
+}
+
+#[test]
+fn greets_a_name_with_spaces_and_unicode() {
+    greet("Ferris the 🦀");
+}
@@ -0,0 +1,12 @@
+// Property checks for the leap-year challenge: thousands of random years, compared against
+// the reference solution. The Gregorian rules are applied to every year, even the ones from
+// before 1582 when the calendar came in, just like the tests do with years 4 and 1600.
+
+#[test]
+fn matches_the_reference_for_every_year() {
+    prop::for_all("year", 1..=9999, |year| {
+        let expected = reference::check_is_leap_year(year.try_into().unwrap());
+        let actual = check_is_leap_year(year.try_into().unwrap());
+        prop::same(expected, actual)
+    });
+}
@@ -0,0 +1,14 @@
+// The reference solution for the leap-year challenge. The property checks compare the learner's
+// check_is_leap_year against this one, so it's `pub` to be reachable from the hidden harness.
+
+fn main() {
+    let year = 2021;
+
+    println!("{} is a leap year: {}", year, check_is_leap_year(year));
+}
+
+/// A year is a leap year if it's divisible by 4, except for centuries,
+/// which are only leap years when they're divisible by 400.
+pub fn check_is_leap_year(year: u32) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
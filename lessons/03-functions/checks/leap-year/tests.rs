@@ -0,0 +1,24 @@
+// Hidden tests for the leap-year challenge. `cargo learn check` compiles them together with
+// examples/leap-year.rs, so they can call check_is_leap_year directly.
+
+macro_rules! leap_years {
+    ($($name:ident: $year:expr => $is_leap:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                assert_eq!(check_is_leap_year($year), $is_leap, "check_is_leap_year({})", $year);
+            }
+        )*
+    };
+}
+
+leap_years! {
+    year_2021_is_not_divisible_by_4: 2021 => false,
+    year_2023_is_not_divisible_by_4: 2023 => false,
+    year_2024_is_divisible_by_4: 2024 => true,
+    year_4_is_divisible_by_4: 4 => true,
+    year_1900_is_a_century_not_divisible_by_400: 1900 => false,
+    year_2100_is_a_century_not_divisible_by_400: 2100 => false,
+    year_2000_is_divisible_by_400: 2000 => true,
+    year_1600_is_divisible_by_400: 1600 => true,
+}
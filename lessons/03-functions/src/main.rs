@@ -53,6 +53,7 @@ fn statements_and_expressions() {
     let x = 5;
 
     // Since statements don't return a value, we cannot do this:
+    // @try-error let-statement
     // let y = (let z = 6); // Uncomment this line to see the error.
 
     // Expression:
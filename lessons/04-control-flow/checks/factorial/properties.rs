@@ -0,0 +1,12 @@
+// Property checks for the factorial challenge: random numbers, compared against the reference
+// solution. 12! is the largest factorial that still fits in an i32 or a u32, so the numbers stay
+// at or below 12 no matter which integer type the learner picked.
+
+#[test]
+fn matches_the_reference() {
+    prop::for_all("number", 0..=12, |number| {
+        let expected = reference::calculate_factorial(number.try_into().unwrap());
+        let actual = calculate_factorial(number.try_into().unwrap());
+        prop::same_number(expected, actual)
+    });
+}
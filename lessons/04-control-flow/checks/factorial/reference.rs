@@ -0,0 +1,127 @@
+// The reference solution for the factorial challenge. The property checks compare the learner's
+// calculate_factorial against this one, so it's `pub` to be reachable from the hidden harness.
+//
+// The challenge asks for all three loop styles, so there's one of each. Factorials grow fast:
+// 21! no longer fits in a u64 and 35! no longer fits in a u128, and multiplying past that panics
+// in a debug build. calculate_factorial_checked says so with `None` instead, and
+// calculate_factorial_big keeps going for as long as there's memory.
+
+use std::fmt;
+
+fn main() {
+    let number = 9;
+
+    match calculate_factorial_checked(number) {
+        Some(factorial) => println!("The factorial of {} is {}", number, factorial),
+        None => println!(
+            "The factorial of {} is {}",
+            number,
+            calculate_factorial_big(number)
+        ),
+    }
+}
+
+pub fn calculate_factorial(number: u64) -> u64 {
+    calculate_factorial_for(number)
+}
+
+pub fn calculate_factorial_loop(number: u64) -> u64 {
+    let mut factorial = 1;
+    let mut n = 1;
+
+    loop {
+        if n > number {
+            break factorial;
+        }
+        factorial *= n;
+        n += 1;
+    }
+}
+
+pub fn calculate_factorial_while(number: u64) -> u64 {
+    let mut factorial = 1;
+    let mut n = 1;
+
+    while n <= number {
+        factorial *= n;
+        n += 1;
+    }
+
+    factorial
+}
+
+pub fn calculate_factorial_for(number: u64) -> u64 {
+    let mut factorial = 1;
+
+    for n in 1..=number {
+        factorial *= n;
+    }
+
+    factorial
+}
+
+/// `None` once the factorial doesn't fit in a u128, from 35 on.
+pub fn calculate_factorial_checked(number: u64) -> Option<u128> {
+    let mut factorial: u128 = 1;
+
+    for n in 1..=number {
+        factorial = factorial.checked_mul(n as u128)?;
+    }
+
+    Some(factorial)
+}
+
+pub fn calculate_factorial_big(number: u64) -> BigUint {
+    let mut factorial = BigUint::one();
+
+    for n in 1..=number {
+        factorial.multiply(n);
+    }
+
+    factorial
+}
+
+/// A whole number of any size, just big enough for factorials: it can only be multiplied.
+pub struct BigUint {
+    /// The number in base 1 000 000 000, lowest digit first, so every digit prints as 9 decimal
+    /// ones and a digit times a u64 still fits in a u128.
+    digits: Vec<u32>,
+}
+
+const BASE: u128 = 1_000_000_000;
+
+impl BigUint {
+    pub fn one() -> Self {
+        BigUint { digits: vec![1] }
+    }
+
+    /// Multiplies digit by digit, the way it's done on paper.
+    pub fn multiply(&mut self, factor: u64) {
+        let mut carry = 0;
+        for digit in &mut self.digits {
+            let product = *digit as u128 * factor as u128 + carry;
+            *digit = (product % BASE) as u32;
+            carry = product / BASE;
+        }
+        while carry > 0 {
+            self.digits.push((carry % BASE) as u32);
+            carry /= BASE;
+        }
+        if factor == 0 {
+            self.digits = vec![0];
+        }
+    }
+}
+
+impl fmt::Display for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut digits = self.digits.iter().rev();
+        if let Some(highest) = digits.next() {
+            write!(f, "{}", highest)?;
+        }
+        for digit in digits {
+            write!(f, "{:09}", digit)?;
+        }
+        Ok(())
+    }
+}
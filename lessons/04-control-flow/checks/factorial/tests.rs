@@ -0,0 +1,22 @@
+// Hidden tests for the factorial challenge. `cargo learn check` compiles them together with
+// examples/factorial.rs, so they can call calculate_factorial directly.
+
+macro_rules! factorials {
+    ($($name:ident: $number:expr => $factorial:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                assert_eq!(calculate_factorial($number), $factorial, "calculate_factorial({})", $number);
+            }
+        )*
+    };
+}
+
+factorials! {
+    factorial_of_0_is_1: 0 => 1,
+    factorial_of_1_is_1: 1 => 1,
+    factorial_of_2: 2 => 2,
+    factorial_of_5: 5 => 120,
+    factorial_of_9: 9 => 362880,
+    factorial_of_12: 12 => 479001600,
+}
@@ -0,0 +1,23 @@
+// Property checks for the gradebook challenge: random points, from well below 0 to well above 100,
+// written into a row and compared against the reference solution.
+
+#[test]
+fn accepts_and_rejects_the_same_rows_as_the_reference() {
+    prop::for_all("points", -1000..=1000, |points| {
+        let row = format!("Ana,{}", points);
+        let expected = reference::parse_row(&row).ok();
+        let actual = parse_row(&row).ok();
+        prop::same(expected, actual)
+    });
+}
+
+// The gradebook is pinned to the 5 to 10 scale whatever scheme the grader challenge uses, so
+// calculate_grade returns a number here rather than the grade of a `scheme` module.
+#[test]
+fn grades_match_the_reference() {
+    prop::for_all("points", 0..=100, |points| {
+        let expected = reference::calculate_grade(points.try_into().unwrap());
+        let actual = calculate_grade(points.try_into().unwrap());
+        prop::same_number(expected, actual)
+    });
+}
@@ -0,0 +1,134 @@
+// The reference solution for the gradebook challenge. The property checks compare the learner's
+// parse_row against this one, so it's `pub` to be reachable from the hidden harness.
+
+use std::collections::BTreeMap;
+
+fn main() {
+    let path = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "examples/gradebook.csv".to_string());
+    let gradebook = std::fs::read_to_string(&path).expect("couldn't read the gradebook");
+
+    let mut students = Vec::new();
+    let mut skipped = Vec::new();
+    for (index, line) in gradebook.lines().enumerate() {
+        let row = line.trim();
+        if row.is_empty() || (index == 0 && row.eq_ignore_ascii_case("student,points")) {
+            continue;
+        }
+        match parse_row(row) {
+            Ok(student) => students.push(student),
+            Err(problem) => skipped.push(format!("line {}: {}", index + 1, problem)),
+        }
+    }
+
+    let width = students
+        .iter()
+        .map(|(name, _)| name.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max("Student".len());
+    println!("{:<width$}  Points  Grade", "Student");
+    for (name, points) in &students {
+        println!("{:<width$}  {:>6}  {:>5}", name, points, calculate_grade(*points));
+    }
+
+    if !skipped.is_empty() {
+        println!();
+        println!("Skipped {} rows:", skipped.len());
+        for problem in &skipped {
+            println!("  {}", problem);
+        }
+    }
+
+    let points: Vec<u32> = students.iter().map(|(_, points)| *points).collect();
+    let (Some(mean), Some(median)) = (mean(&points), median(&points)) else {
+        println!();
+        println!("No students to grade.");
+        return;
+    };
+    println!();
+    println!("Mean: {:.1} points", mean);
+    println!("Median: {:.1} points", median);
+
+    let mut grades = BTreeMap::new();
+    for grade in 5..=10 {
+        grades.insert(grade, 0);
+    }
+    for &points in &points {
+        *grades.entry(calculate_grade(points)).or_insert(0) += 1;
+    }
+    let most = grades.values().copied().max().unwrap_or(0);
+    println!();
+    println!("Grades:");
+    for (grade, count) in grades {
+        println!("{:>5} | {:<most$} {:>2}", grade, "#".repeat(count), count);
+    }
+}
+
+pub fn calculate_grade(points: u32) -> u32 {
+    if points < 50 {
+        5
+    } else if points < 60 {
+        6
+    } else if points < 70 {
+        7
+    } else if points < 80 {
+        8
+    } else if points < 90 {
+        9
+    } else {
+        10
+    }
+}
+
+pub fn parse_row(row: &str) -> Result<(String, u32), String> {
+    let columns: Vec<&str> = row.split(',').map(str::trim).collect();
+    if columns.len() != 2 {
+        return Err(format!(
+            "expected 2 columns, `student,points`, but found {}",
+            columns.len()
+        ));
+    }
+
+    let name = columns[0];
+    if name.is_empty() {
+        return Err("the student's name is missing".to_string());
+    }
+    let points: i64 = match columns[1].parse() {
+        Ok(points) => points,
+        Err(_) => return Err(format!("`{}` isn't a whole number of points", columns[1])),
+    };
+    if !(0..=100).contains(&points) {
+        return Err(format!(
+            "{} points is out of range; points go from 0 to 100",
+            points
+        ));
+    }
+
+    Ok((name.to_string(), points as u32))
+}
+
+/// `None` when there are no points, since an empty class has no mean.
+pub fn mean(points: &[u32]) -> Option<f64> {
+    if points.is_empty() {
+        return None;
+    }
+    let total: u64 = points.iter().map(|&points| points as u64).sum();
+    Some(total as f64 / points.len() as f64)
+}
+
+/// `None` when there are no points, since an empty class has no middle.
+pub fn median(points: &[u32]) -> Option<f64> {
+    if points.is_empty() {
+        return None;
+    }
+    let mut sorted = points.to_vec();
+    sorted.sort();
+    let middle = sorted.len() / 2;
+    if sorted.len() % 2 == 0 {
+        Some((sorted[middle - 1] as f64 + sorted[middle] as f64) / 2.0)
+    } else {
+        Some(sorted[middle] as f64)
+    }
+}
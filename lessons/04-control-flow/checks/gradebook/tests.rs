@@ -0,0 +1,68 @@
+// Hidden tests for the gradebook challenge. `cargo learn check` compiles them together with
+// examples/gradebook.rs, so they can call parse_row, mean and median directly.
+
+#[test]
+fn a_row_is_a_student_and_their_points() {
+    assert_eq!(parse_row("Ana,96"), Ok(("Ana".to_string(), 96)));
+}
+
+#[test]
+fn spaces_around_the_columns_are_ignored() {
+    assert_eq!(parse_row("  Ana Lee , 7 "), Ok(("Ana Lee".to_string(), 7)));
+}
+
+#[test]
+fn both_ends_of_the_range_are_fine() {
+    assert_eq!(parse_row("Ana,0"), Ok(("Ana".to_string(), 0)));
+    assert_eq!(parse_row("Ana,100"), Ok(("Ana".to_string(), 100)));
+}
+
+#[test]
+fn a_row_without_a_comma_is_rejected() {
+    assert!(parse_row("Ana;96").is_err(), "parse_row(\"Ana;96\")");
+}
+
+#[test]
+fn a_row_with_too_many_columns_is_rejected() {
+    assert!(parse_row("Ana,96,10").is_err(), "parse_row(\"Ana,96,10\")");
+}
+
+#[test]
+fn a_row_without_a_name_is_rejected() {
+    assert!(parse_row(",96").is_err(), "parse_row(\",96\")");
+}
+
+#[test]
+fn points_that_arent_a_whole_number_are_rejected() {
+    assert!(parse_row("Ana,abc").is_err(), "parse_row(\"Ana,abc\")");
+    assert!(parse_row("Ana,9.5").is_err(), "parse_row(\"Ana,9.5\")");
+    assert!(parse_row("Ana,").is_err(), "parse_row(\"Ana,\")");
+}
+
+#[test]
+fn points_out_of_range_are_rejected() {
+    assert!(parse_row("Ana,101").is_err(), "parse_row(\"Ana,101\")");
+    assert!(parse_row("Ana,-1").is_err(), "parse_row(\"Ana,-1\")");
+}
+
+#[test]
+fn mean_of_a_class() {
+    assert_eq!(mean(&[96, 49, 73, 88]), Some(76.5), "mean(&[96, 49, 73, 88])");
+    assert_eq!(mean(&[60]), Some(60.0), "mean(&[60])");
+}
+
+#[test]
+fn median_of_an_odd_number_of_points_is_the_middle_one() {
+    assert_eq!(median(&[96, 49, 73]), Some(73.0), "median(&[96, 49, 73])");
+}
+
+#[test]
+fn median_of_an_even_number_of_points_is_the_mean_of_the_middle_two() {
+    assert_eq!(median(&[96, 49, 73, 88]), Some(80.5), "median(&[96, 49, 73, 88])");
+}
+
+#[test]
+fn an_empty_class_has_no_mean_or_median() {
+    assert_eq!(mean(&[]), None, "mean(&[])");
+    assert_eq!(median(&[]), None, "median(&[])");
+}
@@ -0,0 +1,11 @@
+// Property checks for the grader challenge: random points, compared against the reference solution.
+// Grades are compared as text, so a grade can be a number, a `char` or a string.
+
+#[test]
+fn matches_the_reference_for_every_score() {
+    prop::for_all("points", 0..=100, |points| {
+        let expected = reference::calculate_grade(points.try_into().unwrap());
+        let actual = calculate_grade(points.try_into().unwrap());
+        prop::same(expected.to_string(), actual.to_string())
+    });
+}
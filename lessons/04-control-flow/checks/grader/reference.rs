@@ -0,0 +1,24 @@
+// The reference solution for the grader challenge. The property checks compare the learner's
+// calculate_grade against this one, so it's `pub` to be reachable from the hidden harness.
+//
+// It grades on whichever scheme checks/grader/schemes.toml uses, which `learn check` compiles in
+// as the `scheme` module.
+
+fn main() {
+    let points = 96;
+
+    println!(
+        "You've scored {} points. Your final grade is: {}",
+        points,
+        calculate_grade(points)
+    );
+}
+
+pub fn calculate_grade(points: u32) -> &'static str {
+    for &(from, to, grade) in scheme::BANDS {
+        if (from..=to).contains(&points) {
+            return grade;
+        }
+    }
+    panic!("the `{}` scheme has no band for {} points", scheme::NAME, points)
+}
@@ -0,0 +1,28 @@
+// Hidden tests for the grader challenge. `cargo learn check` compiles them together with
+// examples/grader.rs, so they can call calculate_grade directly.
+//
+// Every band of the grading scheme in use is checked at both of its boundaries. Grades are
+// compared as text, so a grade can be a number, a `char` or a string.
+
+#[test]
+fn every_band_starts_and_ends_at_its_grade() {
+    let mut wrong = Vec::new();
+    for &(from, to, grade) in scheme::BANDS {
+        for points in [from, to] {
+            let actual = calculate_grade(points.try_into().unwrap()).to_string();
+            if actual != grade {
+                wrong.push(format!(
+                    "calculate_grade({}) is {}, but should be {}",
+                    points, actual, grade
+                ));
+            }
+        }
+    }
+
+    assert!(
+        wrong.is_empty(),
+        "on the `{}` scheme:\n{}",
+        scheme::NAME,
+        wrong.join("\n")
+    );
+}
@@ -0,0 +1,224 @@
+// The reference solution for the leap-year challenge. The property checks compare the learner's
+// check_is_leap_year against this one, so it's `pub` to be reachable from the hidden harness.
+//
+// It doubles as a small leap-year toolkit for the calendars in use over the centuries: the Julian
+// one, the Gregorian one that replaced it, and the Gregorian rules carried back to before they
+// existed. Lesson 04 builds it as the `leap-year-toolkit` example:
+//
+//     cargo run --example leap-year-toolkit -- 1890..2030
+//     cargo run --example leap-year-toolkit -- 1700..=1800 --calendar gregorian --switchover 1752-9
+//
+// Without a range, it checks 2021 like the challenge does.
+
+use std::process;
+
+fn main() {
+    let mut years = None;
+    // The last --calendar wins, and --switchover only moves the Gregorian calendar's switchover.
+    let mut calendar = Calendar::ProlepticGregorian;
+    let mut switchover = None;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--calendar" => match args.next().as_deref() {
+                Some("gregorian") => calendar = Calendar::Gregorian(Switchover::ROME),
+                Some("julian") => calendar = Calendar::Julian,
+                Some("proleptic") => calendar = Calendar::ProlepticGregorian,
+                _ => usage("--calendar is one of gregorian, julian or proleptic"),
+            },
+            "--switchover" => match args.next().map(|value| value.parse::<Switchover>()) {
+                Some(Ok(value)) => switchover = Some(value),
+                Some(Err(problem)) => usage(&problem),
+                None => usage("--switchover is a year, optionally followed by a month: 1752-9"),
+            },
+            range if years.is_none() => match parse_years(range) {
+                Some(range) => years = Some(range),
+                None => usage("years look like 2021, 1890..2030 or 1890..=2030"),
+            },
+            _ => usage("give a single range of years"),
+        }
+    }
+    if let Some(switchover) = switchover {
+        match calendar {
+            Calendar::Gregorian(_) => calendar = Calendar::Gregorian(switchover),
+            _ => usage("--switchover only applies to --calendar gregorian"),
+        }
+    }
+
+    let (first, last) = years.unwrap_or((2021, 2021));
+    if first > last {
+        usage("the range has no years in it; the first year comes first, as in 1890..2030");
+    }
+    let Some(count) = last
+        .checked_sub(first)
+        .and_then(|years| years.checked_add(1))
+    else {
+        usage("that's more years than can be counted");
+    };
+    for year in first..=last {
+        if is_leap(year, calendar) {
+            println!("{} is a leap year: true", year);
+        } else {
+            println!("{} is a leap year: false", year);
+        }
+    }
+    if last > first {
+        println!(
+            "{} of the {} years from {} to {} are leap years",
+            leap_years(first..=last, calendar).len(),
+            count,
+            first,
+            last
+        );
+    }
+}
+
+fn usage(problem: &str) -> ! {
+    eprintln!("error: {}", problem);
+    eprintln!("usage: leap-year-toolkit [YEARS] [--calendar gregorian|julian|proleptic] [--switchover YEAR[-MONTH]]");
+    process::exit(2);
+}
+
+/// `2021`, `1890..2030` or `1890..=2030`, as the first and last year.
+fn parse_years(text: &str) -> Option<(i32, i32)> {
+    if let Some((first, last)) = text.split_once("..=") {
+        Some((first.parse().ok()?, last.parse().ok()?))
+    } else if let Some((first, end)) = text.split_once("..") {
+        Some((
+            first.parse().ok()?,
+            end.parse::<i32>().ok()?.checked_sub(1)?,
+        ))
+    } else {
+        let year = text.parse().ok()?;
+        Some((year, year))
+    }
+}
+
+/// A year is a leap year if it's divisible by 4, except for centuries,
+/// which are only leap years when they're divisible by 400.
+pub fn check_is_leap_year(year: u32) -> bool {
+    if year % 400 == 0 {
+        true
+    } else if year % 100 == 0 {
+        false
+    } else {
+        year % 4 == 0
+    }
+}
+
+/// Which rules decide the leap years. Years are counted astronomically, so 1 BC is year 0 and
+/// 2 BC is year -1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Calendar {
+    /// Every fourth year is a leap year, with no exceptions.
+    Julian,
+    /// The Gregorian rules, for every year, even those before the calendar was introduced.
+    ProlepticGregorian,
+    /// The Julian calendar until the switchover, and the Gregorian one from then on, as it was
+    /// actually used.
+    Gregorian(Switchover),
+}
+
+/// When a country moved from the Julian to the Gregorian calendar, dropping the days the Julian
+/// calendar had fallen behind by from that month.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Switchover {
+    pub year: i32,
+    pub month: u32,
+}
+
+impl Switchover {
+    /// October 1582, when the Gregorian calendar was introduced: the 4th was followed by the 15th.
+    pub const ROME: Switchover = Switchover {
+        year: 1582,
+        month: 10,
+    };
+    /// September 1752, in Britain and its colonies: the 2nd was followed by the 14th.
+    pub const BRITAIN: Switchover = Switchover {
+        year: 1752,
+        month: 9,
+    };
+
+    /// How many days the Julian calendar had fallen behind by: 10 in 1582, 11 in 1752.
+    pub fn dropped_days(self) -> u32 {
+        let year = self.year - i32::from(self.month <= 2);
+        (year.div_euclid(100) - year.div_euclid(400) - 2).max(0) as u32
+    }
+}
+
+/// `1752-9`, or just `1752` for October of that year. A switchover so late that the calendars
+/// are a whole February apart by then is refused, since there'd be no month left to switch in.
+impl std::str::FromStr for Switchover {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let (year, month) = text.split_once('-').unwrap_or((text, "10"));
+        let year = year
+            .parse()
+            .map_err(|_| format!("`{}` isn't a year", year))?;
+        let switchover = match month.parse() {
+            Ok(month) if (1..=12).contains(&month) => Switchover { year, month },
+            _ => return Err(format!("`{}` isn't a month", month)),
+        };
+        if switchover.dropped_days() >= 28 {
+            return Err(format!(
+                "by {} the calendars are {} days apart, too many to drop in a month",
+                text,
+                switchover.dropped_days()
+            ));
+        }
+        Ok(switchover)
+    }
+}
+
+pub fn is_leap(year: i32, calendar: Calendar) -> bool {
+    let julian = year.rem_euclid(4) == 0;
+    let gregorian = julian && (year.rem_euclid(100) != 0 || year.rem_euclid(400) == 0);
+    match calendar {
+        Calendar::Julian => julian,
+        Calendar::ProlepticGregorian => gregorian,
+        // February decides, so a switchover in January or February already uses the new rules.
+        Calendar::Gregorian(switchover) if year == switchover.year => {
+            if switchover.month <= 2 {
+                gregorian
+            } else {
+                julian
+            }
+        }
+        Calendar::Gregorian(switchover) if year < switchover.year => julian,
+        Calendar::Gregorian(_) => gregorian,
+    }
+}
+
+/// Every leap year among `years`, e.g. `1890..2030`.
+pub fn leap_years(years: impl IntoIterator<Item = i32>, calendar: Calendar) -> Vec<i32> {
+    years
+        .into_iter()
+        .filter(|&year| is_leap(year, calendar))
+        .collect()
+}
+
+/// The days in a month, from 1 for January to 12 for December, less the dropped ones in the month
+/// of a switchover, down to none at all.
+pub fn days_in_month(year: i32, month: u32, calendar: Calendar) -> u32 {
+    let days: u32 = match month {
+        2 if is_leap(year, calendar) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    };
+    match calendar {
+        Calendar::Gregorian(switchover) if (year, month) == (switchover.year, switchover.month) => {
+            days.saturating_sub(switchover.dropped_days())
+        }
+        _ => days,
+    }
+}
+
+/// 365 or 366 days, or fewer in the year of a switchover: 1582 only had 355.
+pub fn days_in_year(year: i32, calendar: Calendar) -> u32 {
+    (1..=12)
+        .map(|month| days_in_month(year, month, calendar))
+        .sum()
+}
@@ -5,8 +5,15 @@ fn main() {
      * TODO: create a function "calculate_factorial" that accepts the number as argument and returns the factorial as a number.
      * Print out the number and its factorial after calling the function.
      * Try implementing the function with all three loop constructs: loop, while, and for.
+     * Give every version a name of its own that starts with "calculate_factorial", e.g. "calculate_factorial_while";
+     * `learn check` makes sure there's one for each loop construct and that they all agree.
      * ex. "The factorial of 7 is 5040"
      *
      * Refresher: a factorial is the product of all positive integers less than or equal to a certain number.
+     *
+     * Bonus: try a number of 21 or more. The factorial no longer fits in a u64, and from 35 on not even in a u128,
+     * so the multiplication overflows and the program panics. Write a "calculate_factorial_checked" that returns
+     * an Option<u128> instead, with `None` when it doesn't fit (`checked_mul` helps), and then one that works
+     * for any number by keeping the digits in a Vec, e.g. "The factorial of 100 is 9332621544394415268169923885626670049071...".
      */
 }
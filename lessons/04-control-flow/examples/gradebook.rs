@@ -0,0 +1,53 @@
+fn main() {
+    let path = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "examples/gradebook.csv".to_string());
+    let gradebook = std::fs::read_to_string(&path).expect("couldn't read the gradebook");
+
+    /*
+     * TODO: grade the whole class in the gradebook, a CSV file with a `student,points` header
+     * followed by one row per student, e.g. "Ana,96". The gradebook always grades from 5 to 10,
+     * with the bands of the grader challenge's "5-10" scheme, whichever scheme the grader itself
+     * is checked against: "calculate_grade" accepts the points as a `u32` and returns the grade as
+     * a number from 5 to 10. Reuse it from the grader if that's the scheme you used there, and add:
+     *
+     *  - "parse_row", which accepts a row of the file and returns the student and their points
+     *    as `Ok((String, u32))`, or an `Err(String)` explaining what's wrong with the row:
+     *    it doesn't have exactly 2 columns, the name is empty, the points aren't a whole number,
+     *    or they're not between 0 and 100.
+     *  - "mean" and "median", which accept the points of every student as a `&[u32]` and return
+     *    their mean and median as an `Option<f64>`: `None` when there are no points at all, since
+     *    an empty class has no mean or middle. The median of an even number of points is the
+     *    mean of the middle two.
+     *
+     * Go through the lines of the file, skipping the header and empty lines, and print:
+     *
+     *  1. A table of every student, their points and their grade.
+     *  2. The rows you had to skip, with their line number and what's wrong with them.
+     *  3. The mean and median points, with one decimal, or "No students to grade." when every
+     *     row was skipped.
+     *  4. How many students got each grade, as a histogram.
+     *
+     * Run it with `cargo run --example gradebook`, which reads examples/gradebook.csv, and
+     * compare your output with this:
+     *
+     *     Student  Points  Grade
+     *     Ana          96     10
+     *     Bojan        49      5
+     *     ...
+     *
+     *     Skipped 4 rows:
+     *       line 6: 104 points is out of range; points go from 0 to 100
+     *       line 8: expected 2 columns, `student,points`, but found 1
+     *       ...
+     *
+     *     Mean: 69.9 points
+     *     Median: 73.0 points
+     *
+     *     Grades:
+     *         5 | ##  2
+     *         6 | #   1
+     *       ...
+     */
+    let _ = gradebook;
+}
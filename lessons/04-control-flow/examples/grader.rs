@@ -2,8 +2,8 @@ fn main() {
     let points = 96;
 
     /*
-     * TODO: create a function "calculate_grade" that accepts the points as argument and returns a grade which is a number.
-     * Grading system:
+     * TODO: create a function "calculate_grade" that accepts the points as argument and returns a grade.
+     * Grading system (your team may grade on another scheme; `learn check` shows it if so):
      *     0 - 49: 5
      *    50 - 59: 6
      *    60 - 69: 7
@@ -31,6 +31,7 @@ fn main() {
      * The condition must always evaluate to a boolean. We'll get an error otherwise.
      * Uncomment the following block and see what happens if the condition doesn't evaluate to a boolean.
      */
+    // @try-error non-bool-condition E0308
     // let y = 10;
     // if y {
     //     println!("Oops!");
@@ -74,6 +75,7 @@ fn main() {
      * In this case though, the return types of all condition blocks must be the same.
      * The following block will not compile. Uncomment it to see the error.
      */
+    // @try-error mismatched-branches E0308
     // let condition = true;
     // let number = if condition { 5 } else { "six" };
 
@@ -40,6 +40,14 @@
  * managing heap data is why ownership exists can help explain why it works the way it does.
  */
 
+/*
+ * Heads up: this lesson intentionally doesn't compile, so you can see the errors that the ownership
+ * rules produce. The lines marked with "~ERROR" are the ones the compiler complains about.
+ *
+ * The lines under #[cfg(feature = "fixed")] show how to fix them. They replace the
+ * #[cfg(not(feature = "fixed"))] lines right above them when you run "cargo run --features fixed".
+ */
+
 fn main() {
     /*
      * There are three ownership rules that we need to keep in mind:
@@ -112,10 +120,14 @@ fn ownership() {
      */
 
     let m = String::from("Lazar");
+    #[cfg(not(feature = "fixed"))]
     let n = m;
+    #[cfg(feature = "fixed")]
+    let n = m.clone();
 
-    println!("The value of n is '{}', and the value of m is '{}'", n, m);
+    println!("The value of n is '{}', and the value of m is '{}'", n, m); // ~ERROR E0382
 
+    // @explains E0382 moving ownership
     /*
      * In this case we can see an error happens when we try to use the variable x. That's the second rule
      * of ownership: "there can only be one owner at a time".
@@ -165,12 +177,15 @@ fn ownership_and_functions() {
      */
 
     let name = String::from("Lazar");
+    #[cfg(not(feature = "fixed"))]
     takes_ownership(name);
+    #[cfg(feature = "fixed")]
+    takes_ownership(name.clone());
 
     let x = 5;
     makes_copy(x);
 
-    println!("We can use x: {}, but not the name variable {}.", x, name);
+    println!("We can use x: {}, but not the name variable {}.", x, name); // ~ERROR E0382
 
     /*
      * We could give back the ownership by returning the variable back to its caller, but it's a bit tedious.
@@ -7,6 +7,12 @@
  * ownership from being transfered, and their value being dropped.
  *
  * We can achieve that through referencing.
+ *
+ * Heads up: this lesson intentionally doesn't compile, so you can see the errors that the borrowing
+ * rules produce. The lines marked with "~ERROR" are the ones the compiler complains about.
+ *
+ * The lines under #[cfg(feature = "fixed")] apply the fixes described in the comments. They replace
+ * the #[cfg(not(feature = "fixed"))] lines right above them when you run "cargo run --features fixed".
  */
 fn main() {
     /*
@@ -35,15 +41,23 @@ fn main() {
      * the function declaration as well (fn calculate_length(name: &String) ...). This allows us to use the
      * name variable in the calculate_length scope, but its value doesn't get dropped when the scope ends. We call
      * the action of creating references "borrowing".
-     *
+     */
+
+    // @explains E0596 modifying borrowed values
+    /*
      * What happens if we try to modify something we're borrowing? We can't! Just like variables are immutable
      * by default, references are immutable by default as well. Here's an example (that won't work) to prove it:
-     * P.S. You won't see the error in your IDE because of the other errors below, but it's there!
      */
 
-    let digits = String::from("abc");
+    #[cfg(not(feature = "fixed"))]
+    let digits = String::from("abc"); // ~ERROR E0596: the mutable borrows below need it to be `mut`
+    #[cfg(feature = "fixed")]
+    let mut digits = String::from("abc");
 
+    #[cfg(not(feature = "fixed"))]
     add_digits(&digits);
+    #[cfg(feature = "fixed")]
+    add_digits(&mut digits);
 
     println!("The digits are now: {}", digits);
 
@@ -57,14 +71,26 @@ fn main() {
      * Go ahead and apply these changes. The error should be gone, and you should see "abcdef" in the output.
      */
 
+    // @explains E0499 mutable references
     /*
      * But, mutable references have one big limitation: you can have only one mutable reference to a certain
      * particular piece of data at a time! We can't do this (make sure you change the digits variable to mutable):
      */
-    let ref1 = &mut digits;
-    let ref2 = &mut digits; // cannot borrow `digits` as mutable more than once at a time
+    #[cfg(not(feature = "fixed"))]
+    {
+        let ref1 = &mut digits;
+        let ref2 = &mut digits; // ~ERROR E0499: cannot borrow `digits` as mutable more than once at a time
 
-    println!("{} {}", ref1, ref2);
+        println!("{} {}", ref1, ref2);
+    }
+    #[cfg(feature = "fixed")]
+    {
+        let ref1 = &mut digits;
+        println!("{}", ref1); // ref1 is not used after this line, so we can borrow digits again
+
+        let ref2 = &mut digits;
+        println!("{}", ref2);
+    }
 
     /*
      * This error says that our code is invalid becase we're borrowing digits more than once in the same scope.
@@ -90,6 +116,7 @@ fn main() {
     let outerRef = &mut digits;
     println!("Outer ref: {}", outerRef);
 
+    // @explains E0502 mixing mutable and immutable references
     /*
      * A similar restriction happens if we combine mutable and immutable references. So this code won't work:
      */
@@ -98,9 +125,19 @@ fn main() {
 
     let r1 = &person;
     let r2 = &person;
-    let r3 = &mut person; // cannot borrow `person` as mutable because it is also borrowed as immutable
+    #[cfg(not(feature = "fixed"))]
+    {
+        let r3 = &mut person; // ~ERROR E0502: cannot borrow `person` as mutable because it is also borrowed as immutable
 
-    println!("{} {} {}", r1, r2, r3);
+        println!("{} {} {}", r1, r2, r3);
+    }
+    #[cfg(feature = "fixed")]
+    {
+        println!("{} {}", r1, r2);
+
+        let r3 = &mut person;
+        println!("{}", r3);
+    }
     /*
      * "What?!? How are we supposed to go around this now?". Remember when we talked about scopes and
      * references? A reference's scope starts from where it's defined until the last time is used. Try
@@ -117,6 +154,15 @@ fn main() {
      * understand the reference scope!
      */
 
+    /*
+     * There's one more thing references guarantee, and it gets a function of its own below: main only
+     * calls it, so the errors in it don't stop the compiler from checking the borrows above.
+     */
+    dangling_references();
+}
+
+fn dangling_references() {
+    // @explains E0106 dangling references
     /*
      * In languages with pointers there's a concept called Dangling References. It's when a pointer points
      * to a memory address that may have been given to someone else, or freeing some memory while preserving
@@ -150,20 +196,42 @@ fn calculate_length(name: &String) -> usize {
     name.len()
 }
 
+#[cfg(not(feature = "fixed"))]
 fn add_digits(digits: &String) {
-    digits.push_str("def"); // Error: cannot borrow `*digits` as mutable, as it is behind a `&` reference
+    digits.push_str("def"); // ~ERROR E0596: cannot borrow `*digits` as mutable, as it is behind a `&` reference
 }
 
-fn dangle() -> &String {
+#[cfg(feature = "fixed")]
+fn add_digits(digits: &mut String) {
+    digits.push_str("def");
+}
+
+#[cfg(not(feature = "fixed"))]
+fn dangle() -> &String { // ~ERROR E0106
     //         ^ error: expected named lifetime parameter
     let s = String::from("Dangle!");
 
     &s
 }
 
-fn dangle_explained() -> &String {
+#[cfg(feature = "fixed")]
+fn dangle() -> String {
+    let s = String::from("Dangle!");
+
+    s // the ownership of s is moved out to the caller, so nothing gets dropped
+}
+
+#[cfg(not(feature = "fixed"))]
+fn dangle_explained() -> &String { // ~ERROR E0106
     //                   ^ we return a reference to a String
     let s = String::from("Dangle!"); // s is a new string
 
     &s // we return a reference to s
 } // here s goes out of scope, and it's dropped. Its memory goes away, but we still have a reference to it!
+
+#[cfg(feature = "fixed")]
+fn dangle_explained() -> String {
+    let s = String::from("Dangle!");
+
+    s
+}
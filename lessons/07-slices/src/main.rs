@@ -30,6 +30,7 @@ fn main() {
 
     println!("========================");
 
+    // @defines String Slices
     /*
      * We can achieve that synchronization using string slices! A string slice is a reference to a part
      * of a string. It looks like this:
@@ -67,8 +68,10 @@ fn main() {
 
     sentence.clear(); // error: cannot borrow `sentence` as mutable because it is also borrowed as immutable
 
+    // @try-error borrow-after-clear E0502
     // println!("The first word after clear is: {}", word);
 
+    // @explains E0502 slices and borrowing
     /*
      * We get the error above. Why? Remember the borrowing rules from last lesson? You can't borrow a variable
      * as mutable if you've already borrowed it as immutable. The clear method borrows the string as a mutable